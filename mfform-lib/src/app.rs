@@ -4,14 +4,18 @@ use std::{
 };
 
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
     terminal::{self, disable_raw_mode, enable_raw_mode},
     ExecutableCommand, QueueableCommand,
 };
 use log::debug;
 use log4rs::Handle;
 
-use crate::form::Form;
+use crate::{form::Form, keymap::Action, table::Table};
 
 /// Result of a Form execute
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -29,6 +33,9 @@ pub enum EventResult {
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum EventHandlerResult {
     Handled(EventResult),
+    /// Handled by an input field, and the last position of the field was
+    /// filled
+    Filled,
     NotHandled,
 }
 
@@ -42,8 +49,11 @@ pub struct App {
 impl App {
     /// Constructor for the App struct which takes in the output where to write
     /// the output of the program
-    ///```
-    ///let app = with_writer().init()?;
+    ///```no_run
+    ///# use mfform_lib::App;
+    ///let mut app = App::with_writer(std::io::stdout());
+    ///app.init()?;
+    ///# Ok::<(), std::io::Error>(())
     ///```
     pub fn with_writer(stdout: Stdout) -> Self {
         let logging_enabled = Arc::new(RwLock::new(false));
@@ -58,8 +68,11 @@ impl App {
     }
 
     /// Initialize the terminal, place it into 'cooked' mode and install panic handler.
-    ///```
-    ///let app = with_writer().init()?;
+    ///```no_run
+    ///# use mfform_lib::App;
+    ///let mut app = App::with_writer(std::io::stdout());
+    ///app.init()?;
+    ///# Ok::<(), std::io::Error>(())
     ///```
     pub fn init(&mut self) -> io::Result<()> {
         self.init_terminal()?;
@@ -83,13 +96,19 @@ impl App {
 
         enable_raw_mode()?;
 
+        // Pasted text arrives as a single event instead of key presses
+        self.stdout.execute(EnableBracketedPaste)?;
+
         Ok(())
     }
 
     fn restore_terminal() -> io::Result<()> {
         disable_raw_mode()?;
 
-        io::stdout().execute(terminal::LeaveAlternateScreen)?;
+        io::stdout()
+            .execute(DisableMouseCapture)?
+            .execute(DisableBracketedPaste)?
+            .execute(terminal::LeaveAlternateScreen)?;
 
         Ok(())
     }
@@ -173,29 +192,29 @@ impl App {
 
     /// Process keyboard input, returning an EventResult indicating wheter the user
     /// submittied, aobrted, toggled debug output or if further input is required.
+    ///
+    /// Keys are looked up in the key map of the form.
     pub fn keyboard_event(&mut self, form: &mut Form, ev: Event) -> io::Result<EventResult> {
-        match ev {
-            Event::Key(k) if k.code == KeyCode::Esc => {
+        match form.keymap.action(&ev) {
+            Some(Action::Abort | Action::Quit) => {
                 return Ok(EventResult::Abort);
             }
-            Event::Key(k) if k.code == KeyCode::Enter => {
+            Some(Action::Submit) => {
                 return Ok(EventResult::Submit);
             }
-            Event::Key(k) if k.code == KeyCode::Left => {
-                form.move_event(k.code);
+            Some(Action::MoveLeft) => {
+                form.move_event(KeyCode::Left);
             }
-            Event::Key(k) if k.code == KeyCode::Right => {
-                form.move_event(k.code);
+            Some(Action::MoveRight) => {
+                form.move_event(KeyCode::Right);
             }
-            Event::Key(k) if k.code == KeyCode::Up => {
-                form.move_event(k.code);
+            Some(Action::MoveUp) => {
+                form.move_event(KeyCode::Up);
             }
-            Event::Key(k) if k.code == KeyCode::Down => {
-                form.move_event(k.code);
+            Some(Action::MoveDown) => {
+                form.move_event(KeyCode::Down);
             }
-            Event::Key(k)
-                if k.code == KeyCode::Char('d') && k.modifiers.contains(KeyModifiers::CONTROL) =>
-            {
+            Some(Action::ToggleDebug) => {
                 return Ok(EventResult::ToggleDebug);
             }
             _ => (),
@@ -207,16 +226,100 @@ impl App {
     /// Executes a form to completion.  This is the event loop of a program under normal
     /// conditions.  Uses the crossterm input events.
     pub fn execute(&mut self, form: &mut Form) -> io::Result<EventResult> {
+        let (columns, rows) = terminal::size()?;
+        form.fit_terminal(columns, rows);
+
+        let (enhancement, mouse) = (form.keymap.needs_enhancement(), form.mouse);
+        self.with_input_modes(enhancement, mouse, |app| app.event_loop(form))
+    }
+
+    /// Executes a table display to completion, the line commands entered are
+    /// in the table when the user submits.  Keys are looked up in the key map
+    /// of the table.
+    pub fn execute_table(&mut self, table: &mut Table) -> io::Result<EventResult> {
+        let (columns, rows) = terminal::size()?;
+        table.fit_terminal(columns, rows);
+
+        let (enhancement, mouse) = (table.keymap.needs_enhancement(), table.mouse);
+        self.with_input_modes(enhancement, mouse, |app| app.table_event_loop(table))
+    }
+
+    /// Run an event loop with keyboard enhancement, when the key map needs
+    /// it, and mouse capture turned on
+    fn with_input_modes(
+        &mut self,
+        enhancement: bool,
+        mouse: bool,
+        event_loop: impl FnOnce(&mut Self) -> io::Result<EventResult>,
+    ) -> io::Result<EventResult> {
+        // Keys like Right-Ctrl are only reported with keyboard enhancement
+        let enhanced = enhancement && terminal::supports_keyboard_enhancement()?;
+        if enhanced {
+            self.stdout.execute(PushKeyboardEnhancementFlags(
+                KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                    | KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES
+                    | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS,
+            ))?;
+        }
+
+        if mouse {
+            self.stdout.execute(EnableMouseCapture)?;
+        }
+
+        let result = event_loop(self);
+
+        if mouse {
+            self.stdout.execute(DisableMouseCapture)?;
+        }
+        if enhanced {
+            self.stdout.execute(PopKeyboardEnhancementFlags)?;
+        }
+
+        result
+    }
+
+    fn table_event_loop(&mut self, table: &mut Table) -> io::Result<EventResult> {
+        loop {
+            table.display(&mut io::stdout())?;
+
+            let ev = event::read()?;
+
+            if let Event::Resize(columns, rows) = ev {
+                table.fit_terminal(columns, rows);
+            }
+
+            debug!("Key event: {:?}", ev);
+
+            match table.keymap.action(&ev) {
+                Some(Action::Quit) => return Ok(EventResult::Abort),
+                Some(Action::ToggleDebug) => {
+                    self.toggle_log_output()?;
+                    continue;
+                }
+                _ => (),
+            }
+
+            match table.event_handler(&ev)? {
+                EventHandlerResult::Handled(EventResult::Submit) => return Ok(EventResult::Submit),
+                EventHandlerResult::Handled(EventResult::Abort) => return Ok(EventResult::Abort),
+                _ => (),
+            }
+        }
+    }
+
+    fn event_loop(&mut self, form: &mut Form) -> io::Result<EventResult> {
         let mut output = EventResult::None;
         loop {
             form.display(&mut io::stdout())?;
 
             let ev = event::read()?;
 
-            if let Event::Key(kev) = ev {
-                if kev == KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL) {
-                    return Ok(EventResult::Abort);
-                }
+            if let Event::Resize(columns, rows) = ev {
+                form.fit_terminal(columns, rows);
+            }
+
+            if form.keymap.action(&ev) == Some(Action::Quit) {
+                return Ok(EventResult::Abort);
             }
 
             debug!("Key event: {:?}", ev);
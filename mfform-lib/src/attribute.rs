@@ -0,0 +1,47 @@
+use crossterm::style;
+
+/// 3279 extended colour attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Color {
+    Blue,
+    Red,
+    Pink,
+    Green,
+    Turquoise,
+    Yellow,
+    White,
+}
+
+impl From<Color> for style::Color {
+    fn from(value: Color) -> Self {
+        match value {
+            Color::Blue => style::Color::Blue,
+            Color::Red => style::Color::Red,
+            Color::Pink => style::Color::Magenta,
+            Color::Green => style::Color::Green,
+            Color::Turquoise => style::Color::Cyan,
+            Color::Yellow => style::Color::Yellow,
+            Color::White => style::Color::White,
+        }
+    }
+}
+
+/// 3279 extended highlighting attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Highlight {
+    Reverse,
+    Blink,
+    Underscore,
+    Intensify,
+}
+
+impl From<Highlight> for style::Attribute {
+    fn from(value: Highlight) -> Self {
+        match value {
+            Highlight::Reverse => style::Attribute::Reverse,
+            Highlight::Blink => style::Attribute::SlowBlink,
+            Highlight::Underscore => style::Attribute::Underlined,
+            Highlight::Intensify => style::Attribute::Bold,
+        }
+    }
+}
@@ -1,48 +1,273 @@
 use crossterm::{
     cursor,
-    event::{Event, KeyCode},
+    event::{Event, KeyCode, MouseButton, MouseEvent, MouseEventKind},
     style,
     terminal::{self, ClearType},
     QueueableCommand,
 };
 use log::debug;
-use std::{
-    cmp::Ordering,
-    io::{self, Stdout, Write},
-};
+use std::io::{self, Stdout, Write};
 
 use crate::{
     app::{EventHandlerResult, EventResult},
-    input::{Input, Select},
+    group::{Group, GroupRow},
+    help_form::HelpForm,
+    history::{Cursor, History},
+    input::{Input, InputKind, Select},
+    keymap::{Action, KeyMap},
     label::Label,
     pos::Pos,
     select_form::SelectForm,
+    shape::{self, Shape},
+    theme::Theme,
 };
 
+/// Number of edits kept for undo
+const HISTORY_SIZE: usize = 100;
+
 /// Normal input form
 #[derive(Debug, Clone)]
 pub struct Form {
     pub(crate) labels: Vec<Label>,
+    pub(crate) shapes: Vec<Shape>,
     pub(crate) inputs: Vec<Input>,
+    pub(crate) groups: Vec<Group>,
+    /// Group being defined, inputs added go into its row template
+    pub(crate) open_group: Option<Group>,
     pub(crate) current_pos: Pos,
+    /// Page shown, the cursor is always on this page
+    pub(crate) page: usize,
+    /// Number of pages, labels, shapes and inputs are added to the last one
+    pub(crate) pages: usize,
+    /// Logical size of the form, including the border column and the
+    /// status row
     pub(crate) size: Pos,
+    /// Part of the form shown on the terminal, the form scrolls when it is
+    /// larger than the viewport
+    pub(crate) viewport: Pos,
+    /// Form position shown in the top left corner of the viewport
+    pub(crate) offset: Pos,
     pub(crate) select_form: Option<SelectForm>,
+    pub(crate) help_form: Option<HelpForm>,
+    pub(crate) theme: Theme,
+    pub(crate) error_message: Option<String>,
+    pub(crate) autoskip: bool,
+    pub(crate) readline: bool,
+    pub(crate) paste_spill: bool,
+    pub(crate) mouse: bool,
+    pub(crate) help: Option<String>,
+    pub(crate) ascii: bool,
+    pub(crate) keymap: KeyMap,
+    pub(crate) history: History,
 }
 
 impl Form {
     /// Construct a Form object, given the input dimensions.
+    ///
+    /// The theme is taken from the environment, see [`Theme::from_env`].
     pub fn new(size: impl Into<Pos>) -> io::Result<Self> {
+        let size = size.into();
+
         Ok(Self {
             labels: Default::default(),
+            shapes: Default::default(),
             inputs: Default::default(),
+            groups: Default::default(),
+            open_group: None,
             current_pos: (0, 0).into(),
-            size: size.into(),
+            page: 0,
+            pages: 1,
+            size,
+            viewport: size,
+            offset: (0, 0).into(),
             select_form: None,
+            help_form: None,
+            theme: Theme::from_env(),
+            error_message: None,
+            autoskip: false,
+            readline: false,
+            paste_spill: false,
+            mouse: false,
+            help: None,
+            ascii: false,
+            keymap: KeyMap::default(),
+            history: History::new(HISTORY_SIZE),
         })
     }
 
+    /// Use the supplied theme when displaying the form
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+
+        self
+    }
+
+    /// Change the theme used when displaying the form
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Move to the next field when the last position of a field is filled,
+    /// unless the field says otherwise
+    pub fn with_autoskip(mut self, autoskip: bool) -> Self {
+        self.autoskip = autoskip;
+
+        self
+    }
+
+    /// Change the auto-skip setting of the form
+    pub fn set_autoskip(&mut self, autoskip: bool) {
+        self.autoskip = autoskip;
+    }
+
+    /// Enable readline style editing keys in fields, Ctrl-A/E/K/U/W and
+    /// Alt-B/F
+    pub fn with_readline(mut self, readline: bool) -> Self {
+        self.readline = readline;
+
+        self
+    }
+
+    /// Enable or disable readline style editing keys
+    pub fn set_readline(&mut self, readline: bool) {
+        self.readline = readline;
+    }
+
+    /// Spill pasted tab separated values into consecutive fields in tab
+    /// order, e.g. a row copied from a spreadsheet
+    pub fn with_paste_spill(mut self, paste_spill: bool) -> Self {
+        self.paste_spill = paste_spill;
+
+        self
+    }
+
+    /// Enable or disable spilling pasted tab separated values across fields
+    pub fn set_paste_spill(&mut self, paste_spill: bool) {
+        self.paste_spill = paste_spill;
+    }
+
+    /// Capture the mouse while the form is shown.  Clicking a field moves the
+    /// cursor there and clicking a legend entry acts as its key.
+    pub fn with_mouse(mut self, mouse: bool) -> Self {
+        self.mouse = mouse;
+
+        self
+    }
+
+    /// Enable or disable mouse support
+    pub fn set_mouse(&mut self, mouse: bool) {
+        self.mouse = mouse;
+    }
+
+    /// Help text shown with F1 on fields without help of their own
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+
+        self
+    }
+
+    /// Change the help text of the form
+    pub fn set_help(&mut self, help: impl Into<String>) {
+        self.help = Some(help.into());
+    }
+
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    /// Draw lines and boxes with plain ASCII, for terminals without box
+    /// drawing characters
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+
+        self
+    }
+
+    /// Enable or disable plain ASCII line drawing
+    pub fn set_ascii(&mut self, ascii: bool) {
+        self.ascii = ascii;
+    }
+
+    /// Change the logical size of the form, it may be larger than the
+    /// viewport
+    pub fn set_size(&mut self, size: impl Into<Pos>) {
+        self.size = size.into();
+    }
+
+    /// Show the form in a viewport of the given size, in the same terms as
+    /// the form size.  The form scrolls to follow the cursor when it is
+    /// larger than the viewport.  [`App::execute`](crate::App::execute) fits
+    /// the viewport to the terminal.
+    pub fn with_viewport(mut self, viewport: impl Into<Pos>) -> Self {
+        self.viewport = viewport.into();
+
+        self
+    }
+
+    /// Change the size of the viewport, see [`Form::with_viewport`]
+    pub fn set_viewport(&mut self, viewport: impl Into<Pos>) {
+        self.viewport = viewport.into();
+    }
+
+    /// Fit the viewport to a terminal, never larger than the form
+    pub(crate) fn fit_terminal(&mut self, columns: u16, rows: u16) {
+        // The border is drawn one column in from the right edge of the form
+        // size, and the legend on the row below it
+        self.viewport = Pos {
+            x: (columns + 1).min(self.size.x),
+            y: rows.saturating_sub(1).min(self.size.y),
+        };
+    }
+
+    /// Use the supplied key bindings, see [`KeyMap`]
+    pub fn with_keymap(mut self, keymap: KeyMap) -> Self {
+        self.keymap = keymap;
+
+        self
+    }
+
+    /// Change the key bindings of the form
+    pub fn set_keymap(&mut self, keymap: KeyMap) {
+        self.keymap = keymap;
+    }
+
+    pub fn keymap(&self) -> &KeyMap {
+        &self.keymap
+    }
+
     // Input handling
     pub(crate) fn event_handler(&mut self, event: &Event) -> io::Result<EventHandlerResult> {
+        let focused = self.focused();
+        let values = self.values();
+        let cursor = self.cursor();
+
+        let result = self.handle_event(event)?;
+        let after = self.values();
+        // Inserting or deleting rows of a group clears the history
+        if after.len() != values.len() {
+            self.focus_changed(None);
+            return Ok(result);
+        }
+
+        // Changes are recorded before fields are formatted on leaving them
+        if !matches!(self.keymap.action(event), Some(Action::Undo | Action::Redo)) {
+            self.history.record(&values, &after, cursor, self.cursor());
+        }
+        self.focus_changed(focused);
+
+        Ok(result)
+    }
+
+    fn handle_event(&mut self, event: &Event) -> io::Result<EventHandlerResult> {
+        // Help is closed with the cursor where it was
+        if let Some(help_form) = self.help_form.as_mut() {
+            let result = help_form.event_handler(event)?;
+            if result == EventHandlerResult::Handled(EventResult::Abort) {
+                self.help_form = None;
+            }
+            return Ok(EventHandlerResult::Handled(EventResult::None));
+        }
+
         // Popup input handling
         if let Some(select_form) = self.select_form.as_mut() {
             let result = select_form.event_handler(event)?;
@@ -63,6 +288,7 @@ impl Form {
                             .map(|s| s.as_str())
                             .unwrap_or_default()
                             .to_string();
+                        f.scroll = 0;
                     };
                     self.current_pos = self
                         .current_field()
@@ -79,124 +305,597 @@ impl Form {
             };
         }
 
+        if let Event::Key(_) = event {
+            self.error_message = None;
+        }
+
+        if let Event::Paste(text) = event {
+            self.paste(text);
+            return Ok(EventHandlerResult::Handled(EventResult::None));
+        }
+
+        if let Event::Mouse(mouse) = event {
+            return self.mouse_event(mouse);
+        }
+
+        // Keys bound to Submit are not passed to the field, a text area would
+        // take Ctrl-J for a new line
+        if self.keymap.action(event) == Some(Action::Submit) {
+            return self.action(Action::Submit);
+        }
+
         let mut current_pos = self.current_pos;
 
-        if let Some(current_field) = self.current_field() {
-            if let EventHandlerResult::Handled(result) =
-                current_field.event_handler(event, &mut current_pos)?
-            {
-                self.current_pos = current_pos;
-                return Ok(EventHandlerResult::Handled(result));
+        let autoskip = self.autoskip;
+        let readline = self.readline;
+        if let Some(current_field) = self.current_field().filter(|f| !f.protected) {
+            let mut result = EventHandlerResult::NotHandled;
+            if readline {
+                result = current_field.readline_event_handler(event, &mut current_pos)?;
+            }
+            if result == EventHandlerResult::NotHandled {
+                result = current_field.event_handler(event, &mut current_pos)?;
+            }
+
+            match result {
+                EventHandlerResult::Handled(result) => {
+                    self.current_pos = current_pos;
+                    self.sync_radio_group();
+                    return Ok(EventHandlerResult::Handled(result));
+                }
+                EventHandlerResult::Filled => {
+                    let skip = current_field.autoskip.unwrap_or(autoskip);
+                    self.current_pos = current_pos;
+                    if skip {
+                        self.next_input();
+                    }
+                    return Ok(EventHandlerResult::Handled(EventResult::None));
+                }
+                EventHandlerResult::NotHandled => (),
             }
         }
 
-        match event {
-            Event::Key(k) if k.code == KeyCode::Esc => {
+        let Some(action) = self.keymap.action(event) else {
+            return Ok(EventHandlerResult::NotHandled);
+        };
+
+        self.action(action)
+    }
+
+    /// Perform a form level action, from a key or a click on the legend
+    fn action(&mut self, action: Action) -> io::Result<EventHandlerResult> {
+        match action {
+            Action::Abort => {
                 return Ok(EventHandlerResult::Handled(EventResult::Abort));
             }
-            Event::Key(k) if k.code == KeyCode::Enter => {
-                return Ok(EventHandlerResult::Handled(EventResult::Submit));
+            Action::Submit => {
+                return Ok(EventHandlerResult::Handled(self.submit()));
             }
-            Event::Key(k) if k.code == KeyCode::Left => {
-                self.move_event(k.code);
+            Action::NewLine => {
+                self.new_line();
             }
-            Event::Key(k) if k.code == KeyCode::Right => {
-                self.move_event(k.code);
+            Action::FirstField => {
+                self.first_input();
             }
-            Event::Key(k) if k.code == KeyCode::Up => {
-                self.move_event(k.code);
+            Action::EraseInput => {
+                let page = self.page;
+                self.inputs
+                    .iter_mut()
+                    .filter(|i| i.page == page && !i.is_protected())
+                    .for_each(Input::clear);
+                self.first_input();
             }
-            Event::Key(k) if k.code == KeyCode::Down => {
-                self.move_event(k.code);
+            Action::Undo => {
+                self.undo();
             }
-            Event::Key(k) if k.code == KeyCode::Tab => {
+            Action::Redo => {
+                self.redo();
+            }
+            Action::ResetField => {
+                if let Some(field) = self.current_field().filter(|f| !f.protected) {
+                    field.reset();
+                    field.enter();
+                }
+                self.sync_radio_group();
+            }
+            Action::ResetForm => {
+                self.inputs
+                    .iter_mut()
+                    .filter(|i| !i.is_protected())
+                    .for_each(Input::reset);
+                if let Some(field) = self.current_field() {
+                    field.enter();
+                }
+            }
+            Action::MoveLeft => {
+                self.move_event(KeyCode::Left);
+            }
+            Action::MoveRight => {
+                self.move_event(KeyCode::Right);
+            }
+            Action::MoveUp => {
+                if !self.scroll_group(false) {
+                    self.move_event(KeyCode::Up);
+                }
+            }
+            Action::MoveDown => {
+                if !self.scroll_group(true) {
+                    self.move_event(KeyCode::Down);
+                }
+            }
+            Action::NextField => {
                 self.next_input();
             }
-            Event::Key(k) if k.code == KeyCode::BackTab => {
+            Action::NextPage => {
+                self.next_page();
+            }
+            Action::PrevPage => {
+                self.prev_page();
+            }
+            Action::InsertRow => {
+                self.insert_row();
+            }
+            Action::DeleteRow => {
+                self.delete_row();
+            }
+            Action::PrevField => {
                 self.prev_input();
             }
-            Event::Key(k) if k.code == KeyCode::F(4) => {
+            Action::Help => {
+                let help = match self.current_field() {
+                    Some(Input {
+                        name,
+                        help: Some(help),
+                        ..
+                    }) => Some((format!("Help for {}", name), help.clone())),
+                    _ => None,
+                }
+                .or_else(|| self.help.clone().map(|help| ("Help".to_string(), help)));
+
+                match help {
+                    Some((title, text)) => {
+                        debug!("Display help: {}", title);
+                        self.help_form = Some(
+                            HelpForm::new(title, &text, self.popup_size())
+                                .with_theme(self.theme)
+                                .with_ascii(self.ascii),
+                        );
+                    }
+                    None => self.error_message = Some("No help available".to_string()),
+                }
+            }
+            Action::Select => {
                 debug!("Display select form");
-                let Some(current_field) = self.current_field() else {
+                let size = self.popup_size();
+                let Some(current_field) = self.current_field().filter(|f| !f.protected) else {
                     return Ok(EventHandlerResult::Handled(EventResult::None));
                 };
 
                 if current_field.select == Select::Single {
                     let mut select_form =
-                        SelectForm::new(&current_field.select_static, (80, 24), Select::Single)?;
+                        SelectForm::new(&current_field.select_static, size, Select::Single)?
+                            .with_theme(self.theme)
+                            .with_ascii(self.ascii)
+                            .with_keymap(self.keymap.clone());
                     select_form.display(&mut std::io::stdout())?;
 
                     self.select_form = Some(select_form);
                 }
             }
-            _ => return Ok(EventHandlerResult::NotHandled),
+            // Handled by the App
+            Action::Quit | Action::ToggleDebug => return Ok(EventHandlerResult::NotHandled),
         }
 
         Ok(EventHandlerResult::Handled(EventResult::None))
     }
 
+    /// Validate all fields before submitting, the cursor is placed on the
+    /// first invalid field.
+    fn submit(&mut self) -> EventResult {
+        // Hidden and protected values are passed through, the user can not
+        // change them
+        for i in 0..self.inputs.len() {
+            if self.inputs[i].is_protected() {
+                continue;
+            }
+            if let Err(e) = self.inputs[i].validate() {
+                debug!("Validation failed: {}", e);
+                self.error_message = Some(e);
+                self.focus_input(i);
+                return EventResult::None;
+            }
+        }
+
+        EventResult::Submit
+    }
+
     pub(crate) fn display(&mut self, stdout: &mut Stdout) -> io::Result<()> {
+        if let Some(help_form) = self.help_form.as_mut() {
+            return help_form.display(stdout);
+        }
         if let Some(select_form) = self.select_form.as_mut() {
             return select_form.display(stdout);
         }
 
+        self.scroll_to_cursor();
+        let border = self.viewport.x.saturating_sub(2);
+        let bottom = self.viewport.y;
+
         // Clear dialog
         stdout
-            .queue(cursor::MoveTo(self.size.x, self.size.y))?
+            .queue(cursor::MoveTo(self.viewport.x, self.viewport.y))?
             .queue(terminal::Clear(ClearType::FromCursorUp))?;
 
         // Border
-        stdout.queue(style::SetForegroundColor(style::Color::DarkGreen))?;
-        for y in 0..24 {
+        let (vertical, horizontal, corner) = shape::border_chars(self.ascii);
+        stdout.queue(style::SetForegroundColor(self.theme.border))?;
+        for y in 0..bottom {
             stdout
-                .queue(cursor::MoveTo(80, y))?
-                .queue(style::Print('│'))?;
+                .queue(cursor::MoveTo(border, y))?
+                .queue(style::Print(vertical))?;
         }
         stdout
-            .queue(cursor::MoveTo(0, 24))?
-            .queue(style::Print("─".repeat(80)))?
-            .queue(style::Print('┘'))?;
+            .queue(cursor::MoveTo(0, bottom))?
+            .queue(style::Print(horizontal.to_string().repeat(border as usize)))?
+            .queue(style::Print(corner))?;
 
-        stdout
-            .queue(cursor::MoveTo(2, 24))?
-            .queue(style::Print(" Esc=Abort "))?
-            .queue(style::Print('─'))?
-            .queue(style::Print(" Enter=Submit "))?;
+        for (x, text, _) in self.legend() {
+            stdout
+                .queue(cursor::MoveTo(x, bottom))?
+                .queue(style::Print(text))?;
+        }
 
-        if self
-            .inputs
-            .iter()
-            .find(|i| i.has_focus(self.current_pos))
-            .filter(|i| i.select != Select::None)
-            .is_some()
-        {
+        self.display_scroll_indicators(stdout)?;
+
+        if let Some((x, text)) = self.page_indicator() {
+            stdout
+                .queue(cursor::MoveTo(x, bottom))?
+                .queue(style::Print(text))?;
+        }
+
+        if let Some(error_message) = &self.error_message {
             stdout
-                .queue(cursor::MoveTo(82 - 6 - 10, 24))?
-                .queue(style::SetForegroundColor(style::Color::DarkGreen))?
-                .queue(style::Print(" F4 - Select "))?;
+                .queue(cursor::MoveTo(
+                    (self.viewport.x / 2).saturating_sub(error_message.len() as u16 / 2),
+                    self.viewport.y - 1,
+                ))?
+                .queue(style::SetForegroundColor(self.theme.error))?
+                .queue(style::Print(error_message))?;
+        } else if let Some(hint) = self.focused().and_then(|i| self.inputs[i].hint.as_ref()) {
+            stdout
+                .queue(cursor::MoveTo(1, self.viewport.y - 1))?
+                .queue(style::SetForegroundColor(self.theme.hint))?
+                .queue(style::Print(hint))?;
+        }
+
+        // Lines and boxes go under labels and fields
+        for shape in self.shapes.iter().filter(|s| s.page == self.page) {
+            stdout.queue(style::SetForegroundColor(
+                self.theme.attribute_color(shape.color, self.theme.border),
+            ))?;
+            for (pos, text) in shape
+                .segments(self.ascii)
+                .into_iter()
+                .filter_map(|(pos, text)| self.clip(pos, &text))
+            {
+                stdout
+                    .queue(cursor::MoveTo(pos.x, pos.y))?
+                    .queue(style::Print(text))?;
+            }
         }
 
-        for label in self.labels.clone() {
+        for label in self.labels.iter().filter(|l| l.page == self.page) {
+            let Some((pos, text)) = self.clip(label.pos, &label.text) else {
+                continue;
+            };
+
             stdout
-                .queue(cursor::MoveTo(label.pos.x, label.pos.y))?
-                .queue(style::SetForegroundColor(style::Color::White))?
-                .queue(style::Print(label.text))?;
+                .queue(cursor::MoveTo(pos.x, pos.y))?
+                .queue(style::SetForegroundColor(
+                    self.theme.attribute_color(label.color, self.theme.label),
+                ))?;
+            if let Some(highlight) = label.highlight {
+                stdout.queue(style::SetAttribute(highlight.into()))?;
+            }
+            stdout
+                .queue(style::Print(text))?
+                .queue(style::SetAttribute(style::Attribute::Reset))?;
         }
 
-        for input in self.inputs.clone() {
-            display_generic(stdout, &input)?;
+        for input in self
+            .inputs
+            .iter()
+            .filter(|i| i.page == self.page && !i.hidden && !i.scrolled_out)
+        {
+            // Fields are drawn at their position in the viewport
+            if let Some(pos) = self.in_view(input) {
+                let mut input = input.clone();
+                input.pos = pos;
+                display_generic(stdout, &self.theme, &input)?;
+            }
         }
 
-        stdout.queue(cursor::MoveTo(self.current_pos.x, self.current_pos.y))?;
+        self.display_group_indicators(stdout)?;
+
+        let cursor = self.current_pos.to_screen(self.offset);
+        stdout.queue(cursor::MoveTo(cursor.x, cursor.y))?;
         stdout.queue(cursor::SetCursorStyle::SteadyUnderScore)?;
 
         stdout.flush()
     }
 
+    /// Size of popups, drawn with their border where the form border is
+    fn popup_size(&self) -> Pos {
+        Pos {
+            x: self.viewport.x.saturating_sub(2),
+            y: self.viewport.y,
+        }
+    }
+
+    /// Column and text of the page number on the bottom border, towards the
+    /// right end clear of the scroll arrow
+    fn page_indicator(&self) -> Option<(u16, String)> {
+        if self.pages < 2 {
+            return None;
+        }
+
+        let text = format!(" Page {} of {} ", self.page + 1, self.pages);
+        let border = self.viewport.x.saturating_sub(2);
+
+        Some((border.saturating_sub(text.len() as u16 + 2), text))
+    }
+
+    /// Columns and rows of the form in view, the viewport less the border
+    /// column and the status row.  Unlimited in directions the form does not
+    /// scroll.
+    fn visible(&self) -> Pos {
+        Pos {
+            x: if self.size.x > self.viewport.x {
+                self.viewport.x.saturating_sub(2)
+            } else {
+                u16::MAX
+            },
+            y: if self.size.y > self.viewport.y {
+                self.viewport.y.saturating_sub(1)
+            } else {
+                u16::MAX
+            },
+        }
+    }
+
+    /// Largest scroll offset, the last rows and columns of the form are shown
+    /// at the bottom and right of the viewport
+    fn max_offset(&self) -> Pos {
+        Pos {
+            x: self.size.x.saturating_sub(self.viewport.x),
+            y: self.size.y.saturating_sub(self.viewport.y),
+        }
+    }
+
+    /// Scroll the viewport just enough to bring the cursor into view
+    pub(crate) fn scroll_to_cursor(&mut self) {
+        fn follow(offset: u16, cursor: u16, visible: u16) -> u16 {
+            if cursor < offset {
+                cursor
+            } else if cursor >= offset.saturating_add(visible) {
+                cursor + 1 - visible
+            } else {
+                offset
+            }
+        }
+
+        let visible = self.visible();
+        let max = self.max_offset();
+        self.offset = Pos {
+            x: follow(self.offset.x, self.current_pos.x, visible.x).min(max.x),
+            y: follow(self.offset.y, self.current_pos.y, visible.y).min(max.y),
+        };
+    }
+
+    /// The part of a text in view, with its position on screen
+    fn clip(&self, pos: Pos, text: &str) -> Option<(Pos, String)> {
+        let visible = self.visible();
+        if pos.y < self.offset.y || pos.y - self.offset.y >= visible.y {
+            return None;
+        }
+
+        let screen = pos.to_screen(self.offset);
+        if pos.x.saturating_add(text.chars().count() as u16) <= self.offset.x
+            || screen.x >= visible.x
+        {
+            return None;
+        }
+
+        let text: String = text
+            .chars()
+            .skip(self.offset.x.saturating_sub(pos.x) as usize)
+            .take((visible.x - screen.x) as usize)
+            .collect();
+
+        Some((screen, text))
+    }
+
+    /// Screen position of an input, if the whole input is in view
+    fn in_view(&self, input: &Input) -> Option<Pos> {
+        let visible = self.visible();
+        let end = Pos {
+            x: input.pos.x + input.length,
+            y: input.pos.y + input.height,
+        };
+
+        (input.pos.x >= self.offset.x
+            && input.pos.y >= self.offset.y
+            && end.x <= self.offset.x.saturating_add(visible.x)
+            && end.y <= self.offset.y.saturating_add(visible.y))
+        .then(|| input.pos.to_screen(self.offset))
+    }
+
+    /// Scroll arrows, up, down, left and right
+    fn arrows(&self) -> (char, char, char, char) {
+        if self.ascii {
+            ('^', 'v', '<', '>')
+        } else {
+            ('▲', '▼', '◄', '►')
+        }
+    }
+
+    /// Arrows on the border when part of the form is scrolled out of view
+    fn display_scroll_indicators(&self, stdout: &mut Stdout) -> io::Result<()> {
+        let (up, down, left, right) = self.arrows();
+        let border = self.viewport.x.saturating_sub(2);
+        let bottom = self.viewport.y;
+        let max = self.max_offset();
+
+        for (show, x, y, arrow) in [
+            (self.offset.y > 0, border, 0, up),
+            (
+                self.offset.y < max.y,
+                border,
+                bottom.saturating_sub(2),
+                down,
+            ),
+            (self.offset.x > 0, 0, bottom, left),
+            (
+                self.offset.x < max.x,
+                border.saturating_sub(1),
+                bottom,
+                right,
+            ),
+        ] {
+            if show {
+                stdout
+                    .queue(cursor::MoveTo(x, y))?
+                    .queue(style::Print(arrow))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Arrows right of repeating groups with rows scrolled out of view
+    fn display_group_indicators(&self, stdout: &mut Stdout) -> io::Result<()> {
+        let (up, down, _, _) = self.arrows();
+        stdout.queue(style::SetForegroundColor(self.theme.border))?;
+
+        for group in self
+            .groups
+            .iter()
+            .filter(|g| g.page == self.page && g.rows > g.visible)
+        {
+            let (top, height) = group.row_span();
+            let bottom = top + group.visible as u16 * height - 1;
+
+            for (show, y, arrow) in [
+                (group.scroll > 0, top, up),
+                (group.scroll + group.visible < group.rows, bottom, down),
+            ] {
+                let Some((pos, text)) = self
+                    .clip((group.right() + 1, y).into(), &arrow.to_string())
+                    .filter(|_| show)
+                else {
+                    continue;
+                };
+
+                stdout
+                    .queue(cursor::MoveTo(pos.x, pos.y))?
+                    .queue(style::Print(text))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Legend entries on the bottom border with their column and action
+    fn legend(&self) -> Vec<(u16, String, Action)> {
+        let mut legend = Vec::new();
+
+        let help = self.help.is_some()
+            || self
+                .focused()
+                .is_some_and(|i| self.inputs[i].help.is_some());
+
+        let mut x = 2;
+        for (action, text) in [
+            (Action::Abort, "Abort"),
+            (Action::Submit, "Submit"),
+            (Action::Help, "Help"),
+        ] {
+            if action == Action::Help && !help {
+                continue;
+            }
+            if let Some(key) = self.keymap.key(action) {
+                let text = format!(" {}={} ", key, text);
+                let width = text.chars().count() as u16;
+                legend.push((x, text, action));
+                // Entries are separated by a border character
+                x += width + 1;
+            }
+        }
+
+        if let Some(key) = self.keymap.key(Action::Select).filter(|_| {
+            self.focused()
+                .map(|i| &self.inputs[i])
+                .filter(|i| i.select != Select::None && !i.protected)
+                .is_some()
+        }) {
+            legend.push((
+                self.viewport.x.saturating_sub(6 + 10),
+                format!(" {} - Select ", key),
+                Action::Select,
+            ));
+        }
+
+        legend
+    }
+
+    /// Clicking a field moves the cursor, clicking the legend acts as the
+    /// key shown and the wheel scrolls text areas
+    fn mouse_event(&mut self, mouse: &MouseEvent) -> io::Result<EventHandlerResult> {
+        let screen = Pos {
+            x: mouse.column,
+            y: mouse.row,
+        };
+        let pos = screen.to_form(self.offset);
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if screen.y == self.viewport.y => {
+                let action = self.legend().into_iter().find_map(|(x, text, action)| {
+                    (screen.x >= x && screen.x < x + text.chars().count() as u16).then_some(action)
+                });
+                if let Some(action) = action {
+                    return self.action(action);
+                }
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self
+                    .inputs
+                    .iter()
+                    .any(|i| i.page == self.page && i.has_focus(pos))
+                {
+                    self.current_pos = pos;
+                }
+            }
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+                let down = mouse.kind == MouseEventKind::ScrollDown;
+                let page = self.page;
+                if let Some(input) = self
+                    .inputs
+                    .iter_mut()
+                    .find(|i| i.page == page && i.is_text_area() && i.has_focus(pos))
+                {
+                    input.scroll_rows(down);
+                }
+            }
+            _ => return Ok(EventHandlerResult::NotHandled),
+        }
+
+        Ok(EventHandlerResult::Handled(EventResult::None))
+    }
+
     pub(crate) fn move_event(&mut self, code: KeyCode) {
         self.current_pos = match code {
             KeyCode::Left => Pos {
-                x: self.current_pos.x.checked_sub(1).unwrap_or_default(),
+                x: self.current_pos.x.saturating_sub(1),
                 y: self.current_pos.y,
             },
             KeyCode::Right => Pos {
@@ -205,7 +904,7 @@ impl Form {
             },
             KeyCode::Up => Pos {
                 x: self.current_pos.x,
-                y: self.current_pos.y.checked_sub(1).unwrap_or_default(),
+                y: self.current_pos.y.saturating_sub(1),
             },
             KeyCode::Down => Pos {
                 x: self.current_pos.x,
@@ -216,147 +915,782 @@ impl Form {
         .constrain(self.size)
     }
 
-    pub(crate) fn find_next_input(&mut self) -> Option<Pos> {
-        let mut inputs = self.inputs.clone();
-        inputs.sort();
+    /// Input indexes in tab order, page by page.  On each page fields with a
+    /// tab index come first and the rest follow in screen order.  Fields that
+    /// are not tab stops are left out.
+    fn tab_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.inputs.len())
+            .filter(|i| self.inputs[*i].tab_stop)
+            .collect();
+        order.sort_by_key(|i| {
+            let input = &self.inputs[*i];
+            (
+                input.page,
+                input.tab_index.is_none(),
+                input.tab_index,
+                self.screen_key(input),
+            )
+        });
 
-        let mut i = inputs.iter();
-        let mut first_pos = None;
-
-        loop {
-            // Return None if no input fields
-            let Some(input) = i.next() else {
-                return first_pos;
-            };
+        order
+    }
 
-            // Set first pos if none set
-            if first_pos.is_none() {
-                first_pos = Some(input.pos);
+    /// Order of inputs on screen, the rows of a repeating group are kept
+    /// together whether they are in view or not
+    fn screen_key(&self, input: &Input) -> (u16, usize, Pos) {
+        match &input.group_row {
+            Some(GroupRow { group, row, field }) => {
+                let group = &self.groups[*group];
+                (group.row_span().0, *row, group.fields[*field].pos)
             }
+            None => (input.pos.y, 0, input.pos),
+        }
+    }
 
-            match self.current_pos.cmp(&input.pos) {
-                // Current pos is before the input pos
-                Ordering::Less => {
-                    return Some(input.pos);
-                }
+    /// Tab stops on the current page in screen order
+    fn screen_order(&self, order: &[usize]) -> Vec<usize> {
+        let mut screen: Vec<usize> = order
+            .iter()
+            .copied()
+            .filter(|i| self.inputs[*i].page == self.page && !self.inputs[*i].scrolled_out)
+            .collect();
+        screen.sort_by_key(|i| self.inputs[*i].pos);
 
-                // We are on the first character if an input field
-                // We should return the next input field
-                Ordering::Equal => {
-                    if let Some(next) = i.next() {
-                        // There is a next field available, return it's pos
-                        return Some(next.pos);
-                    } else {
-                        // No next field, wrap around to first_pos
-                        return first_pos;
-                    }
-                }
-                _ => (),
-            }
-        }
+        screen
     }
 
-    pub(crate) fn find_prev_input(&mut self) -> Option<Pos> {
-        let mut inputs = self.inputs.clone();
-        inputs.sort();
+    /// Next field in tab order, wrapping around from the last page to the
+    /// first.  When the cursor is not on a tab stop the next one on screen is
+    /// used.
+    pub(crate) fn find_next_input(&self) -> Option<usize> {
+        let order = self.tab_order();
+
+        match self.focused_position(&order) {
+            Some(i) => order.get(i + 1).or(order.first()).copied(),
+            None => self
+                .screen_order(&order)
+                .into_iter()
+                .find(|i| self.inputs[*i].pos > self.current_pos)
+                .or(order.first().copied()),
+        }
+    }
 
-        let mut i = inputs.iter().rev();
+    /// Previous field in tab order, wrapping around from the first page to
+    /// the last.  When the cursor is not on a tab stop the previous one on
+    /// screen is used.
+    pub(crate) fn find_prev_input(&self) -> Option<usize> {
+        let order = self.tab_order();
 
-        loop {
-            // Return None if no input fields
-            let Some(input) = i.next() else {
-                debug!("Returning last");
-                return inputs.last().map(|w| w.pos);
-            };
+        match self.focused_position(&order) {
+            Some(0) => order.last().copied(),
+            Some(i) => order.get(i - 1).copied(),
+            None => self
+                .screen_order(&order)
+                .into_iter()
+                .rev()
+                .find(|i| self.inputs[*i].pos < self.current_pos)
+                .or(order.last().copied()),
+        }
+    }
 
-            debug!("Comparing {:?} to {:?}", self.current_pos, input.pos);
+    /// Index into the tab order of the field under the cursor
+    fn focused_position(&self, order: &[usize]) -> Option<usize> {
+        let focused = self.focused()?;
 
-            match self.current_pos.cmp(&input.pos) {
-                // Current pos is before the input pos
-                Ordering::Greater => {
-                    return Some(input.pos);
-                }
+        order.iter().position(|i| *i == focused)
+    }
 
-                // We are on the first character if an input field
-                // We should return the next input field
-                Ordering::Equal => {
-                    if let Some(next) = i.next() {
-                        // There is a next field available, return it's pos
-                        return Some(next.pos);
-                    } else {
-                        // No next field, wrap around to first_pos
-                        return inputs.iter().map(|w| w.pos).last();
-                    }
-                }
-                _ => (),
-            }
+    /// Move the cursor to the start of an input, switching to its page and
+    /// scrolling its row into view
+    fn focus_input(&mut self, index: usize) {
+        self.page = self.inputs[index].page;
+        if let Some(GroupRow { group, row, .. }) = self.inputs[index].group_row {
+            self.groups[group].scroll_to(row);
+            self.layout_group(group);
         }
+        self.current_pos = self.inputs[index].pos;
     }
 
     /// Move cursor to next input
     pub fn next_input(&mut self) {
-        if let Some(pos) = self.find_next_input() {
-            self.current_pos = pos;
+        if let Some(i) = self.find_next_input() {
+            self.focus_input(i);
         }
     }
 
     /// Move cursor to previous input
     pub fn prev_input(&mut self) {
-        if let Some(pos) = self.find_prev_input() {
-            self.current_pos = pos;
+        if let Some(i) = self.find_prev_input() {
+            self.focus_input(i);
         }
     }
 
-    /// Get input field under cursor
-    pub fn current_field(&mut self) -> Option<&mut Input> {
+    /// Start a new page, labels, shapes and inputs added after this go on
+    /// the new page.  Field values are shared by all pages.
+    pub fn new_page(&mut self) {
+        self.pages += 1;
+    }
+
+    /// Number of pages
+    pub fn pages(&self) -> usize {
+        self.pages
+    }
+
+    /// Page shown, counting from 0
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    /// Show the next page, the cursor is placed on its first input.  Returns
+    /// false on the last page.
+    pub fn next_page(&mut self) -> bool {
+        self.show_page(self.page + 1)
+    }
+
+    /// Show the previous page, the cursor is placed on its first input.
+    /// Returns false on the first page.
+    pub fn prev_page(&mut self) -> bool {
+        self.page > 0 && self.show_page(self.page - 1)
+    }
+
+    fn show_page(&mut self, page: usize) -> bool {
+        if page >= self.pages || page == self.page {
+            return false;
+        }
+
+        self.page = page;
+        self.current_pos = (0, 0).into();
+        self.first_input();
+
+        true
+    }
+
+    /// Index of the input field under the cursor
+    fn focused(&self) -> Option<usize> {
         self.inputs
+            .iter()
+            .position(|f| f.page == self.page && f.has_focus(self.current_pos))
+    }
+
+    /// Cursor position relative to the focused input
+    fn cursor(&self) -> Cursor {
+        match self.focused() {
+            Some(i) => Cursor {
+                input: Some(i),
+                pos: self.current_pos.to_screen(self.inputs[i].pos),
+            },
+            None => Cursor {
+                input: None,
+                pos: self.current_pos,
+            },
+        }
+    }
+
+    /// Put the cursor back on the input it was on, switching page and
+    /// scrolling the row of a group into view
+    fn restore_cursor(&mut self, cursor: Cursor) {
+        match cursor.input.filter(|i| *i < self.inputs.len()) {
+            Some(i) => {
+                self.focus_input(i);
+                self.current_pos = cursor.pos.to_form(self.inputs[i].pos);
+            }
+            None => self.current_pos = cursor.pos,
+        }
+    }
+
+    /// Let the fields know when the cursor moves between them, invalid
+    /// values are reported as the field is left.
+    fn focus_changed(&mut self, previous: Option<usize>) {
+        let current = self.focused();
+        if current == previous {
+            return;
+        }
+
+        if let Some(input) = previous.and_then(|i| self.inputs.get_mut(i)) {
+            input.leave();
+            if let Err(e) = input.validate() {
+                self.error_message = Some(e);
+            }
+        }
+        if let Some(input) = current.and_then(|i| self.inputs.get_mut(i)) {
+            input.enter();
+        }
+    }
+
+    /// Paste text into the field under the cursor, see [`Input::paste`].
+    /// With paste spill enabled each tab separated value goes into the next
+    /// field in tab order, values beyond the last field are dropped.
+    pub(crate) fn paste(&mut self, text: &str) {
+        let text = text.trim_end_matches(['\r', '\n']);
+        let values: Vec<&str> = if self.paste_spill {
+            text.split('\t').collect()
+        } else {
+            vec![text]
+        };
+
+        let Some(focused) = self.focused() else {
+            return;
+        };
+        let order = self.tab_order();
+        let mut fields = vec![focused];
+        if let Some(i) = self.focused_position(&order) {
+            fields.extend(&order[i + 1..]);
+        }
+
+        let mut current_pos = self.current_pos;
+        for (value, i) in values.into_iter().zip(fields) {
+            if i != focused {
+                self.focus_input(i);
+                current_pos = self.current_pos;
+            }
+
+            self.inputs[i].paste(value, &mut current_pos);
+        }
+        self.current_pos = current_pos;
+    }
+
+    /// Current values of all inputs
+    fn values(&self) -> Vec<String> {
+        self.inputs.iter().map(|i| i.value.clone()).collect()
+    }
+
+    /// Undo the last change to field values, the cursor returns to where it
+    /// was before the change.  Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(edit) = self.history.undo() else {
+            return false;
+        };
+
+        for (i, before, _) in edit.changes {
+            self.inputs[i].value = before;
+            self.inputs[i].scroll = 0;
+        }
+        self.restore_cursor(edit.cursor_before);
+
+        true
+    }
+
+    /// Redo the last undone change.  Returns false if there is nothing to
+    /// redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(edit) = self.history.redo() else {
+            return false;
+        };
+
+        for (i, _, after) in edit.changes {
+            self.inputs[i].value = after;
+            self.inputs[i].scroll = 0;
+        }
+        self.restore_cursor(edit.cursor_after);
+
+        true
+    }
+
+    /// Set the value of a field, for a radio button group the id of the
+    /// button to select.  The change can be undone.  Returns false if there
+    /// is no field with that name.
+    pub fn set_value(&mut self, name: &str, value: impl Into<String>) -> bool {
+        let value = value.into();
+        let values = self.values();
+        let focused = self.focused();
+        let mut found = false;
+
+        for (i, input) in self
+            .inputs
             .iter_mut()
-            .find(|f| f.has_focus(self.current_pos))
+            .enumerate()
+            .filter(|(_, i)| i.name == name)
+        {
+            input.value = value.clone();
+            input.scroll = 0;
+            if Some(i) == focused {
+                input.enter();
+            } else {
+                input.leave();
+            }
+            found = true;
+        }
+
+        let cursor = self.cursor();
+        self.history.record(&values, &self.values(), cursor, cursor);
+
+        found
+    }
+
+    /// Move cursor to the first input on the page in tab order
+    pub fn first_input(&mut self) {
+        if let Some(i) = self
+            .tab_order()
+            .into_iter()
+            .find(|i| self.inputs[*i].page == self.page)
+        {
+            self.focus_input(i);
+        }
+    }
+
+    /// Move cursor to the first input on a row below the cursor, wrapping
+    /// around to the first input
+    pub fn new_line(&mut self) {
+        let next = self
+            .inputs
+            .iter()
+            .filter(|i| i.page == self.page && i.tab_stop && !i.scrolled_out)
+            .map(|i| i.pos)
+            .filter(|pos| pos.y > self.current_pos.y)
+            .min();
+
+        match next {
+            Some(pos) => self.current_pos = pos,
+            None => self.first_input(),
+        }
+    }
+
+    /// Get input field under cursor
+    pub fn current_field(&mut self) -> Option<&mut Input> {
+        self.inputs
+            .iter_mut()
+            .find(|f| f.page == self.page && f.has_focus(self.current_pos))
     }
 
     /// Add text label to form at specified position with supplied text
     #[allow(dead_code)]
     pub fn add_text(mut self, pos: impl Into<Pos>, text: impl Into<String>) -> Self {
-        self.labels.push(Label::new_label(pos, text));
+        self.add_label(Label::new_label(pos, text));
 
         self
     }
 
     /// Add a label to the form, label must be created beforehand
-    pub fn add_label(&mut self, label: Label) {
+    pub fn add_label(&mut self, mut label: Label) {
+        label.page = self.pages - 1;
         self.labels.push(label);
     }
 
+    /// Add a line or box to the form, shape must be created beforehand
+    pub fn add_shape(&mut self, mut shape: Shape) {
+        shape.page = self.pages - 1;
+        self.shapes.push(shape);
+    }
+
     /// Add an input to the form, label must be created beforehand
-    pub fn add_input(&mut self, input: Input) {
+    ///
+    /// Radio buttons join the group of earlier buttons with the same name,
+    /// a selected button selects it for the whole group.  The group is reset
+    /// to the button selected initially.
+    pub fn add_input(&mut self, mut input: Input) {
+        if let Some(group) = self.open_group.as_mut() {
+            group.fields.push(input);
+            return;
+        }
+
+        input.page = self.pages - 1;
+        self.push_input(input);
+    }
+
+    fn push_input(&mut self, mut input: Input) {
+        if matches!(input.kind, InputKind::Radio { .. }) {
+            let group_value = self
+                .radio_group(&input.name)
+                .next()
+                .map(|i| (i.value.clone(), i.default_value.clone()));
+
+            match group_value {
+                Some(_) if input.is_checked() => {
+                    let value = input.value.clone();
+                    let default_value = input.default_value.clone();
+                    self.radio_group(&input.name).for_each(|i| {
+                        i.value = value.clone();
+                        i.default_value = default_value.clone();
+                    });
+                }
+                Some((value, default_value)) => {
+                    input.value = value;
+                    input.default_value = default_value;
+                }
+                None => (),
+            }
+        }
+
         self.inputs.push(input);
     }
 
+    fn radio_group<'a>(&'a mut self, name: &'a str) -> impl Iterator<Item = &'a mut Input> {
+        self.inputs
+            .iter_mut()
+            .filter(move |i| i.name == name && matches!(i.kind, InputKind::Radio { .. }))
+    }
+
+    /// Copy the value of the radio button under the cursor to the rest of its
+    /// group
+    fn sync_radio_group(&mut self) {
+        let Some(field) = self.current_field() else {
+            return;
+        };
+
+        if !matches!(field.kind, InputKind::Radio { .. }) {
+            return;
+        }
+
+        let name = field.name.clone();
+        let value = field.value.clone();
+        self.radio_group(&name)
+            .for_each(|i| i.value = value.clone());
+    }
+
     /// Add select option to an imput field in the form.
     ///
     /// Adding an option to a field enables the 'Select (F4)' function on
     /// the input field.  This in effect changes the input field to have
     /// SingleSelct behavior.  Options will be displayed in the order they
     /// are added.
-    pub fn add_select(&mut self, input: String, id: String, value: String) {
-        let input = self.inputs.iter_mut().find(|i| i.name == input);
-
-        if let Some(input) = input {
+    ///
+    /// The name of a field in a repeating group adds the option to the field
+    /// on every row.
+    pub fn add_select(&mut self, input: String, id: String, value: String) -> Result<(), String> {
+        self.update_inputs(&input, |input| {
             if input.select == Select::None {
                 input.select = Select::Single;
             }
 
-            input.select_static.push((id, value));
+            input.select_static.push((id.clone(), value.clone()));
             debug!("List: {:?}", input.select_static);
+        })
+    }
+
+    /// Add help text to an input field, shown with F1.  Help added to the
+    /// same field more than once is shown as separate lines.
+    pub fn add_help(&mut self, input: &str, text: &str) -> Result<(), String> {
+        self.update_inputs(input, |input| {
+            input.help = Some(match input.help.take() {
+                Some(help) => format!("{}\n{}", help, text),
+                None => text.to_string(),
+            });
+        })
+    }
+
+    /// Set the hint of an input field, shown on the status row while the
+    /// cursor is on the field
+    pub fn set_hint(&mut self, input: &str, hint: impl Into<String>) -> Result<(), String> {
+        let hint = hint.into();
+        self.update_inputs(input, |input| input.hint = Some(hint.clone()))
+    }
+
+    /// Set the placeholder of an input field, shown while the field is empty
+    pub fn set_placeholder(
+        &mut self,
+        input: &str,
+        placeholder: impl Into<String>,
+    ) -> Result<(), String> {
+        let placeholder = placeholder.into();
+        self.update_inputs(input, |input| input.placeholder = Some(placeholder.clone()))
+    }
+
+    /// Apply a change to the inputs with a name.  The name of a field in a
+    /// repeating group matches the field on every row, and in the row
+    /// template of a group being defined.  Fails when there is no input with
+    /// the name.
+    fn update_inputs(
+        &mut self,
+        name: &str,
+        mut update: impl FnMut(&mut Input),
+    ) -> Result<(), String> {
+        let groups = &self.groups;
+        let mut found = false;
+
+        for input in self
+            .inputs
+            .iter_mut()
+            .filter(|i| {
+                i.name == name
+                    || i.group_row
+                        .as_ref()
+                        .is_some_and(|r| groups[r.group].fields[r.field].name == name)
+            })
+            .chain(
+                self.open_group
+                    .iter_mut()
+                    .flat_map(|g| g.fields.iter_mut())
+                    .filter(|i| i.name == name),
+            )
+        {
+            update(input);
+            found = true;
+        }
+
+        if !found {
+            return Err(format!("Input {} not found", name));
+        }
+
+        Ok(())
+    }
+
+    /// Start a repeating group, inputs added until [`Form::end_group`] make
+    /// up its row template.  Groups can not be nested.
+    pub fn begin_group(&mut self, group: Group) -> Result<(), String> {
+        if let Some(open) = &self.open_group {
+            return Err(format!(
+                "GROUP {} started before END of GROUP {}",
+                group.name, open.name
+            ));
+        }
+        self.open_group = Some(group);
+
+        Ok(())
+    }
+
+    /// Finish the repeating group started with [`Form::begin_group`] and add
+    /// it to the form
+    pub fn end_group(&mut self) -> Result<(), String> {
+        let group = self
+            .open_group
+            .take()
+            .ok_or_else(|| "END without GROUP".to_string())?;
+        self.add_group(group);
+
+        Ok(())
+    }
+
+    /// The group started with [`Form::begin_group`] and not yet ended
+    pub fn open_group(&self) -> Option<&Group> {
+        self.open_group.as_ref()
+    }
+
+    /// Add a repeating group to the form, each row gets a copy of the fields
+    /// in the row template.  See [`Group`].
+    pub fn add_group(&mut self, mut group: Group) {
+        let index = self.groups.len();
+        group.page = self.pages - 1;
+
+        for row in 0..group.rows {
+            for (field, template) in group.fields.iter().enumerate() {
+                let mut input = template.clone();
+                input.name = group.field_name(row, field);
+                input.page = group.page;
+                input.group_row = Some(GroupRow {
+                    group: index,
+                    row,
+                    field,
+                });
+                self.push_input(input);
+            }
+        }
+
+        self.groups.push(group);
+        self.layout_group(index);
+    }
+
+    /// Place the inputs of a group on the rows in view
+    fn layout_group(&mut self, index: usize) {
+        let group = &self.groups[index];
+
+        for input in self.inputs.iter_mut() {
+            let Some(GroupRow { row, field, .. }) =
+                input.group_row.as_ref().filter(|r| r.group == index)
+            else {
+                continue;
+            };
+
+            match group.field_pos(*row, *field) {
+                Some(pos) => {
+                    input.pos = pos;
+                    input.scrolled_out = false;
+                }
+                None => input.scrolled_out = true,
+            }
+        }
+    }
+
+    /// Group row of the input under the cursor
+    fn current_row(&self) -> Option<GroupRow> {
+        self.focused()
+            .and_then(|i| self.inputs[i].group_row.clone())
+    }
+
+    /// Scroll the group under the cursor instead of moving off the first or
+    /// last row in view.  Returns false if the group was not scrolled.
+    fn scroll_group(&mut self, down: bool) -> bool {
+        let Some(current) = self.current_row() else {
+            return false;
+        };
+        let group = &mut self.groups[current.group];
+        let (top, height) = group.row_span();
+        let bottom = top + group.visible as u16 * height - 1;
+
+        if down && self.current_pos.y == bottom && group.scroll + group.visible < group.rows {
+            group.scroll += 1;
+        } else if !down && self.current_pos.y == top && group.scroll > 0 {
+            group.scroll -= 1;
         } else {
-            panic!("Input not found");
+            return false;
         }
+
+        self.layout_group(current.group);
+
+        true
     }
 
-    /// Place the cursor on the next available input, or on 0,0 if no inputs
-    /// are present.
+    /// Copy the values on a row of a group to another row
+    fn copy_row(&mut self, group: usize, from: usize, to: usize) {
+        let values: Vec<(usize, String)> = self
+            .inputs
+            .iter()
+            .filter_map(|i| {
+                i.group_row
+                    .as_ref()
+                    .filter(|r| r.group == group && r.row == from)
+                    .map(|r| (r.field, i.value.clone()))
+            })
+            .collect();
+
+        for input in self.inputs.iter_mut() {
+            let Some(row) = input
+                .group_row
+                .as_ref()
+                .filter(|r| r.group == group && r.row == to)
+            else {
+                continue;
+            };
+
+            if let Some((_, value)) = values.iter().find(|(field, _)| *field == row.field) {
+                input.value = value.clone();
+                input.scroll = 0;
+            }
+        }
+    }
+
+    /// Move the cursor to a field on a row of a group
+    fn focus_row(&mut self, group: usize, row: usize, field: usize) {
+        let target = GroupRow { group, row, field };
+
+        if let Some(i) = self
+            .inputs
+            .iter()
+            .position(|i| i.group_row.as_ref() == Some(&target))
+        {
+            self.focus_input(i);
+        }
+    }
+
+    /// Insert a row below the current row of a repeating group, the cursor
+    /// moves to the same field on the new row.  Rows can not be inserted
+    /// beyond the maximum number of rows of the group.  Returns false if
+    /// the cursor is not in a group or the group is full.
+    pub fn insert_row(&mut self) -> bool {
+        let Some(current) = self.current_row() else {
+            return false;
+        };
+        let group = &self.groups[current.group];
+        if group.rows >= group.max_rows {
+            self.error_message = Some(format!("No more than {} rows", group.max_rows));
+            return false;
+        }
+
+        if let Some(i) = self.focused() {
+            self.inputs[i].leave();
+        }
+
+        // The new last row is a copy of the current row, the values below
+        // the current row are moved down to make room
+        let last = group.rows;
+        let inputs: Vec<Input> = self
+            .inputs
+            .iter()
+            .filter(|i| {
+                i.group_row
+                    .as_ref()
+                    .is_some_and(|r| r.group == current.group && r.row == current.row)
+            })
+            .map(|i| {
+                let mut input = i.clone();
+                if let Some(row) = input.group_row.as_mut() {
+                    row.row = last;
+                    input.name = group.field_name(last, row.field);
+                }
+                input
+            })
+            .collect();
+        let at = self
+            .inputs
+            .iter()
+            .rposition(|i| {
+                i.group_row
+                    .as_ref()
+                    .is_some_and(|r| r.group == current.group)
+            })
+            .map_or(self.inputs.len(), |i| i + 1);
+        self.inputs.splice(at..at, inputs);
+        self.groups[current.group].rows += 1;
+
+        for row in (current.row + 2..=last).rev() {
+            self.copy_row(current.group, row - 1, row);
+        }
+        self.inputs
+            .iter_mut()
+            .filter(|i| {
+                i.group_row
+                    .as_ref()
+                    .is_some_and(|r| r.group == current.group && r.row == current.row + 1)
+            })
+            .for_each(Input::reset);
+
+        self.history.clear();
+        self.focus_row(current.group, current.row + 1, current.field);
+
+        true
+    }
+
+    /// Delete the current row of a repeating group, the rows below move up.
+    /// The last row left is cleared rather than deleted.  Returns false if
+    /// the cursor is not in a group.
+    pub fn delete_row(&mut self) -> bool {
+        let Some(current) = self.current_row() else {
+            return false;
+        };
+
+        let last = self.groups[current.group].rows - 1;
+        for row in current.row..last {
+            self.copy_row(current.group, row + 1, row);
+        }
+
+        let in_last_row = |i: &Input| {
+            i.group_row
+                .as_ref()
+                .is_some_and(|r| r.group == current.group && r.row == last)
+        };
+        if last == 0 {
+            self.inputs
+                .iter_mut()
+                .filter(|i| in_last_row(i))
+                .for_each(Input::reset);
+        } else {
+            self.inputs.retain(|i| !in_last_row(i));
+            self.groups[current.group].rows -= 1;
+        }
+
+        self.history.clear();
+        let rows = self.groups[current.group].rows;
+        self.focus_row(current.group, current.row.min(rows - 1), current.field);
+
+        true
+    }
+
+    /// Place the cursor on the initial cursor field, or the first input in
+    /// tab order, showing its page.  The cursor is placed on 0,0 of the first
+    /// page if no inputs are present.
     pub fn place_cursor(mut self) -> Self {
-        self.current_pos = self.find_next_input().unwrap_or((0, 0).into());
+        match self
+            .inputs
+            .iter()
+            .position(|i| i.initial_cursor)
+            .or_else(|| self.tab_order().first().copied())
+        {
+            Some(i) => self.focus_input(i),
+            None => {
+                self.page = 0;
+                self.current_pos = (0, 0).into();
+            }
+        }
+        self.focus_changed(None);
 
         self
     }
@@ -373,77 +1707,1081 @@ impl Form {
     }
 
     /// Return an array of input field name and values
-    pub fn get_field_and_data(&self) -> Vec<(&str, &str)> {
-        let mut output = Vec::new();
+    ///
+    /// Radio button groups are only returned once, with the id of the
+    /// selected button as value.  Values are normalised according to the
+    /// field format, see [`Input::output_value`].  Hidden fields are
+    /// included, fields with output turned off are not.
+    pub fn get_field_and_data(&self) -> Vec<(&str, String)> {
+        let mut output: Vec<(&str, String)> = Vec::new();
 
-        for input in &self.inputs {
-            output.push((input.name.as_str(), input.value.as_str()));
+        for input in self.inputs.iter().filter(|i| i.output) {
+            if matches!(input.kind, InputKind::Radio { .. })
+                && output.iter().any(|(name, _)| *name == input.name)
+            {
+                continue;
+            }
+
+            output.push((input.name.as_str(), input.output_value()));
         }
 
         output
     }
+
+    /// Return the field names and values as a JSON object, the same fields
+    /// as [`Form::get_field_and_data`].  Repeating groups are arrays with an
+    /// object for each row.  All values are strings.
+    pub fn get_json(&self) -> String {
+        enum Value {
+            Field(String),
+            Rows(Vec<Vec<String>>),
+        }
+
+        let mut object: Vec<(String, Value)> = Vec::new();
+        for (name, value) in self.get_field_and_data() {
+            let group_row = self
+                .inputs
+                .iter()
+                .find(|i| i.name == name)
+                .and_then(|i| i.group_row.as_ref());
+
+            let Some(GroupRow { group, row, field }) = group_row else {
+                object.push((name.to_string(), Value::Field(json_string(&value))));
+                continue;
+            };
+
+            let group = &self.groups[*group];
+            let member = format!(
+                "{}:{}",
+                json_string(&group.fields[*field].name),
+                json_string(&value)
+            );
+            match object.iter_mut().find(|(n, _)| *n == group.name) {
+                Some((_, Value::Rows(rows))) => {
+                    if rows.len() <= *row {
+                        rows.resize(row + 1, Vec::new());
+                    }
+                    rows[*row].push(member);
+                }
+                _ => {
+                    let mut rows = vec![Vec::new(); row + 1];
+                    rows[*row].push(member);
+                    object.push((group.name.clone(), Value::Rows(rows)));
+                }
+            }
+        }
+
+        let members: Vec<String> = object
+            .into_iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    Value::Field(value) => value,
+                    Value::Rows(rows) => {
+                        let rows: Vec<String> = rows
+                            .into_iter()
+                            .map(|row| format!("{{{}}}", row.join(",")))
+                            .collect();
+                        format!("[{}]", rows.join(","))
+                    }
+                };
+                format!("{}:{}", json_string(&name), value)
+            })
+            .collect();
+
+        format!("{{{}}}", members.join(","))
+    }
+}
+
+/// Quote a string for JSON output
+fn json_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+
+    quoted
 }
 
-fn display_string(stdout: &mut Stdout, input: &Input) -> io::Result<()> {
+fn display_string(stdout: &mut Stdout, theme: &Theme, input: &Input) -> io::Result<()> {
+    let changed = theme.attribute_color(input.color, theme.field_changed);
+    let unchanged = theme.attribute_color(input.color, theme.field);
+    let value = input.edited_value();
+
     stdout
         .queue(cursor::MoveTo(input.pos.x, input.pos.y))?
-        .queue(style::SetAttribute(style::Attribute::Underlined))?;
-    for i in 0..(input.length as usize) {
-        match (
-            input.value.chars().nth(i),
-            input.default_value.chars().nth(i),
-        ) {
+        .queue(style::SetAttribute(field_attribute(input)))?;
+    for i in input.scroll..(input.scroll + input.length as usize) {
+        match (value.chars().nth(i), input.default_value.chars().nth(i)) {
             (Some(s), Some(d)) if s != d => {
                 stdout
-                    .queue(style::SetForegroundColor(style::Color::DarkRed))?
+                    .queue(style::SetForegroundColor(changed))?
                     .queue(style::Print(s))?;
             }
             (Some(s), Some(_)) => {
                 stdout
-                    .queue(style::SetForegroundColor(style::Color::DarkGreen))?
+                    .queue(style::SetForegroundColor(unchanged))?
                     .queue(style::Print(s))?;
             }
             (Some(s), None) => {
                 stdout
-                    .queue(style::SetForegroundColor(style::Color::DarkRed))?
+                    .queue(style::SetForegroundColor(changed))?
                     .queue(style::Print(s))?;
             }
             _ => {
                 stdout
-                    .queue(style::SetForegroundColor(style::Color::DarkGreen))?
+                    .queue(style::SetForegroundColor(unchanged))?
                     .queue(style::Print(" "))?;
             }
         }
     }
 
-    stdout.queue(style::SetAttribute(style::Attribute::NoUnderline))?;
+    stdout.queue(style::SetAttribute(style::Attribute::Reset))?;
+
+    display_scroll_indicators(stdout, theme, input)?;
 
     Ok(())
 }
 
-fn display_password(stdout: &mut Stdout, input: &Input) -> io::Result<()> {
-    let pass_len = input.value.chars().count();
+fn display_password(stdout: &mut Stdout, theme: &Theme, input: &Input) -> io::Result<()> {
+    let pass_len = input
+        .value
+        .chars()
+        .count()
+        .saturating_sub(input.scroll)
+        .min(input.length as usize);
 
     // We only get called if there is a mask_char
     let mask_char = input.mask_char.unwrap();
     stdout
         .queue(cursor::MoveTo(input.pos.x, input.pos.y))?
-        .queue(style::SetAttribute(style::Attribute::Underlined))?
-        .queue(style::SetForegroundColor(style::Color::DarkRed))?
+        .queue(style::SetAttribute(field_attribute(input)))?
+        .queue(style::SetForegroundColor(
+            theme.attribute_color(input.color, theme.field_changed),
+        ))?
         .queue(style::Print(mask_char.to_string().repeat(pass_len)))?
-        .queue(style::SetForegroundColor(style::Color::DarkGreen))?
+        .queue(style::SetForegroundColor(
+            theme.attribute_color(input.color, theme.field),
+        ))?
         .queue(style::Print(" ".repeat(input.length as usize - pass_len)))?
-        .queue(style::SetAttribute(style::Attribute::NoUnderline))?;
+        .queue(style::SetAttribute(style::Attribute::Reset))?;
+
+    display_scroll_indicators(stdout, theme, input)?;
 
     Ok(())
 }
 
-fn display_generic(stdout: &mut Stdout, input: &Input) -> io::Result<()> {
-    if input.mask_char.is_some() {
-        display_password(stdout, input)?;
+/// Show '<' and '>' just outside a field when part of the value is scrolled
+/// out of view.
+fn display_scroll_indicators(stdout: &mut Stdout, theme: &Theme, input: &Input) -> io::Result<()> {
+    stdout.queue(style::SetForegroundColor(theme.border))?;
+
+    if input.scroll > 0 && input.pos.x > 0 {
+        stdout
+            .queue(cursor::MoveTo(input.pos.x - 1, input.pos.y))?
+            .queue(style::Print('<'))?;
+    }
+
+    if input.value.chars().count() > input.scroll + input.length as usize {
+        stdout
+            .queue(cursor::MoveTo(input.pos.x + input.length, input.pos.y))?
+            .queue(style::Print('>'))?;
+    }
+
+    Ok(())
+}
+
+fn display_text_area(stdout: &mut Stdout, theme: &Theme, input: &Input) -> io::Result<()> {
+    let color = if input.value == input.default_value {
+        theme.attribute_color(input.color, theme.field)
     } else {
-        display_string(stdout, input)?;
+        theme.attribute_color(input.color, theme.field_changed)
+    };
+    let rows = input.text_area_rows();
+
+    stdout
+        .queue(style::SetAttribute(field_attribute(input)))?
+        .queue(style::SetForegroundColor(color))?;
+    for y in 0..input.height {
+        let text: String = rows
+            .get(input.scroll + y as usize)
+            .map(|row| input.value.chars().skip(row.start).take(row.len).collect())
+            .unwrap_or_default();
+
+        stdout
+            .queue(cursor::MoveTo(input.pos.x, input.pos.y + y))?
+            .queue(style::Print(format!(
+                "{:width$}",
+                text,
+                width = input.length as usize
+            )))?;
     }
 
+    stdout.queue(style::SetAttribute(style::Attribute::Reset))?;
+
     Ok(())
 }
+
+/// Field highlighting, fields are underscored unless the input says otherwise.
+/// Protected fields are not underscored.
+fn field_attribute(input: &Input) -> style::Attribute {
+    let default = if input.protected {
+        style::Attribute::NoUnderline
+    } else {
+        style::Attribute::Underlined
+    };
+
+    input.highlight.map(Into::into).unwrap_or(default)
+}
+
+/// Check boxes are shown as [x], radio buttons as (*)
+fn display_toggle(stdout: &mut Stdout, theme: &Theme, input: &Input) -> io::Result<()> {
+    let (open, close) = match input.kind {
+        InputKind::Radio { .. } => ('(', ')'),
+        _ => ('[', ']'),
+    };
+    let mark = match (&input.kind, input.is_checked()) {
+        (InputKind::Radio { .. }, true) => '*',
+        (_, true) => 'x',
+        (_, false) => ' ',
+    };
+
+    stdout
+        .queue(cursor::MoveTo(input.pos.x, input.pos.y))?
+        .queue(style::SetForegroundColor(theme.label))?
+        .queue(style::Print(open))?
+        .queue(style::SetForegroundColor(
+            theme.attribute_color(input.color, theme.field_changed),
+        ))?
+        .queue(style::SetAttribute(field_attribute(input)))?
+        .queue(style::Print(mark))?
+        .queue(style::SetAttribute(style::Attribute::Reset))?
+        .queue(style::SetForegroundColor(theme.label))?
+        .queue(style::Print(close))?;
+
+    Ok(())
+}
+
+fn display_generic(stdout: &mut Stdout, theme: &Theme, input: &Input) -> io::Result<()> {
+    if input.kind != InputKind::Text {
+        display_toggle(stdout, theme, input)?;
+    } else if input.mask_char.is_some() {
+        display_password(stdout, theme, input)?;
+    } else if input.is_text_area() {
+        display_text_area(stdout, theme, input)?;
+    } else {
+        display_string(stdout, theme, input)?;
+    }
+
+    if input.kind == InputKind::Text && input.is_blank() {
+        if let Some(placeholder) = &input.placeholder {
+            display_placeholder(stdout, theme, input, placeholder)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Placeholder text over the first row of an empty field, dimmed so it is
+/// not mistaken for a default value
+fn display_placeholder(
+    stdout: &mut Stdout,
+    theme: &Theme,
+    input: &Input,
+    placeholder: &str,
+) -> io::Result<()> {
+    let text: String = placeholder.chars().take(input.length as usize).collect();
+
+    stdout
+        .queue(cursor::MoveTo(input.pos.x, input.pos.y))?
+        .queue(style::SetAttribute(field_attribute(input)))?
+        .queue(style::SetAttribute(style::Attribute::Dim))?
+        .queue(style::SetForegroundColor(theme.placeholder))?
+        .queue(style::Print(text))?
+        .queue(style::SetAttribute(style::Attribute::Reset))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    #[test]
+    fn radio_group() {
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(Input::builder((0, 0), 10, "name").build());
+        form.add_input(Input::radio((0, 2), "size", "small").build());
+        form.add_input(
+            Input::radio((0, 3), "size", "medium")
+                .with_checked(true)
+                .build(),
+        );
+        form.add_input(Input::radio((0, 4), "size", "large").build());
+
+        assert_eq!(
+            form.get_field_and_data(),
+            vec![("name", "".to_string()), ("size", "medium".to_string())]
+        );
+
+        form.current_pos = (0, 4).into();
+        form.event_handler(&Event::Key(KeyCode::Char(' ').into()))
+            .unwrap();
+
+        assert_eq!(
+            form.get_field_and_data(),
+            vec![("name", "".to_string()), ("size", "large".to_string())]
+        );
+        assert!(!form.inputs[2].is_checked());
+        assert!(form.inputs[3].is_checked());
+    }
+
+    #[test]
+    fn submit_validates() {
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(Input::builder((0, 0), 10, "name").build());
+        form.add_input(
+            Input::builder((0, 2), 0, "date")
+                .with_format(crate::format::Format::Date(crate::format::DateFormat::Iso))
+                .with_value("2023-02-29")
+                .build(),
+        );
+
+        let enter = Event::Key(KeyCode::Enter.into());
+
+        assert_eq!(
+            form.event_handler(&enter).unwrap(),
+            EventHandlerResult::Handled(EventResult::None)
+        );
+        assert!(form.error_message.is_some());
+        assert_eq!(form.current_pos, (0, 2).into());
+
+        form.inputs[1].value = "2024-02-29".to_string();
+
+        assert_eq!(
+            form.event_handler(&enter).unwrap(),
+            EventHandlerResult::Handled(EventResult::Submit)
+        );
+    }
+
+    #[test]
+    fn submit_skips_protected() {
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(Input::builder((0, 0), 10, "name").build());
+        form.add_input(
+            Input::hidden("born")
+                .with_format(crate::format::Format::Date(crate::format::DateFormat::Iso))
+                .with_value("2023-02-29")
+                .build(),
+        );
+        form.add_input(
+            Input::builder((0, 2), 6, "count")
+                .with_format(crate::format::Format::Number(Default::default()))
+                .with_value("many")
+                .with_protected(true)
+                .build(),
+        );
+        let mut form = form.place_cursor();
+
+        assert_eq!(
+            form.event_handler(&Event::Key(KeyCode::Enter.into()))
+                .unwrap(),
+            EventHandlerResult::Handled(EventResult::Submit)
+        );
+        assert_eq!(form.error_message, None);
+        assert_eq!(form.current_pos, (0, 0).into());
+        assert_eq!(
+            form.get_field_and_data()[1],
+            ("born", "2023-02-29".to_string())
+        );
+    }
+
+    #[test]
+    fn number_field_exit() {
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(Input::builder((0, 0), 10, "name").build());
+        form.add_input(
+            Input::builder((0, 2), 6, "count")
+                .with_format(crate::format::Format::Number(Default::default()))
+                .with_value("42")
+                .build(),
+        );
+        let mut form = form.place_cursor();
+        form.event_handler(&Event::Key(KeyCode::Tab.into()))
+            .unwrap();
+
+        assert_eq!(form.inputs[1].value, "42");
+
+        form.event_handler(&Event::Key(KeyCode::Char('7').into()))
+            .unwrap();
+        form.event_handler(&Event::Key(KeyCode::Tab.into()))
+            .unwrap();
+
+        assert_eq!(form.inputs[1].value, "    72");
+        assert_eq!(form.get_field_and_data()[1], ("count", "72".to_string()));
+    }
+
+    #[test]
+    fn autoskip() {
+        let mut form = Form::new((80, 24)).unwrap().with_autoskip(true);
+        form.add_input(Input::builder((0, 0), 10, "name").build());
+        form.add_input(Input::builder((0, 2), 2, "code").build());
+        form.add_input(
+            Input::builder((0, 4), 2, "noskip")
+                .with_autoskip(false)
+                .build(),
+        );
+        form.add_input(Input::builder((0, 6), 2, "last").build());
+
+        form.current_pos = (0, 2).into();
+        for c in "abcd".chars() {
+            form.event_handler(&Event::Key(KeyCode::Char(c).into()))
+                .unwrap();
+        }
+
+        assert_eq!(form.inputs[1].value, "ab");
+        assert_eq!(form.inputs[2].value, "cd");
+        assert_eq!(form.current_pos, (1, 4).into());
+    }
+
+    #[test]
+    fn navigation_keys() {
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(Input::builder((10, 0), 10, "first").build());
+        form.add_input(Input::builder((40, 0), 10, "second").build());
+        form.add_input(Input::builder((30, 2), 10, "third").with_value("x").build());
+        form.add_input(Input::checkbox((10, 2), "check").with_checked(true).build());
+
+        form.current_pos = (45, 0).into();
+        form.event_handler(&Event::Key(KeyEvent::new(
+            KeyCode::Enter,
+            KeyModifiers::SHIFT,
+        )))
+        .unwrap();
+        assert_eq!(form.current_pos, (10, 2).into());
+
+        form.new_line();
+        assert_eq!(form.current_pos, (10, 0).into());
+
+        form.current_pos = (35, 2).into();
+        form.event_handler(&Event::Key(KeyCode::Home.into()))
+            .unwrap();
+        assert_eq!(form.current_pos, (10, 0).into());
+
+        form.current_pos = (35, 2).into();
+        form.event_handler(&Event::Key(KeyEvent::new(KeyCode::End, KeyModifiers::ALT)))
+            .unwrap();
+        assert_eq!(form.current_pos, (10, 0).into());
+        assert_eq!(form.inputs[2].value, "");
+        assert!(!form.inputs[3].is_checked());
+    }
+
+    #[test]
+    fn undo_redo_reset() {
+        let ctrl = |c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(
+            Input::builder((0, 0), 10, "first")
+                .with_value("abc")
+                .with_default_value("abc")
+                .build(),
+        );
+        form.add_input(Input::radio((0, 2), "size", "s").build());
+        form.add_input(
+            Input::radio((10, 2), "size", "m")
+                .with_checked(true)
+                .build(),
+        );
+        form.current_pos = (3, 0).into();
+
+        form.event_handler(&Event::Key(KeyCode::Char('d').into()))
+            .unwrap();
+        form.event_handler(&Event::Key(KeyCode::Backspace.into()))
+            .unwrap();
+        form.event_handler(&Event::Key(KeyCode::Backspace.into()))
+            .unwrap();
+        assert_eq!(form.inputs[0].value, "ab");
+
+        form.event_handler(&ctrl('z')).unwrap();
+        form.event_handler(&ctrl('z')).unwrap();
+        assert_eq!(form.inputs[0].value, "abcd");
+        assert_eq!(form.current_pos, (4, 0).into());
+
+        form.event_handler(&ctrl('y')).unwrap();
+        assert_eq!(form.inputs[0].value, "abc");
+        assert_eq!(form.current_pos, (3, 0).into());
+
+        form.event_handler(&ctrl('r')).unwrap();
+        assert_eq!(form.inputs[0].value, "abc");
+
+        assert!(form.set_value("size", "s"));
+        assert!(!form.set_value("missing", "x"));
+        assert!(form.inputs[1].is_checked());
+        form.set_value("first", "xyz");
+
+        form.event_handler(&Event::Key(KeyEvent::new(
+            KeyCode::Char('r'),
+            KeyModifiers::ALT,
+        )))
+        .unwrap();
+        assert_eq!(form.inputs[0].value, "abc");
+        assert!(form.inputs[2].is_checked());
+        assert!(!form.inputs[1].is_checked());
+
+        form.event_handler(&ctrl('z')).unwrap();
+        assert_eq!(form.inputs[0].value, "xyz");
+        assert!(form.inputs[1].is_checked());
+    }
+
+    #[test]
+    fn paste() {
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(Input::builder((0, 0), 5, "first").build());
+        form.add_input(Input::builder((10, 0), 5, "second").build());
+        form.add_input(Input::builder((20, 0), 5, "third").build());
+        form.current_pos = (0, 0).into();
+
+        form.event_handler(&Event::Paste("abc\tdefghij\r\n".into()))
+            .unwrap();
+        assert_eq!(form.inputs[0].value, "abc d");
+        assert_eq!(form.inputs[1].value, "");
+
+        form.set_paste_spill(true);
+        form.current_pos = (10, 0).into();
+        form.event_handler(&Event::Paste("abc\tdefghij\tx\ty".into()))
+            .unwrap();
+        assert_eq!(form.inputs[1].value, "abc");
+        assert_eq!(form.inputs[2].value, "defgh");
+        assert_eq!(form.inputs[0].value, "abc d");
+        assert_eq!(form.current_pos, (24, 0).into());
+
+        // A paste is undone as a whole
+        assert!(form.undo());
+        assert_eq!(form.inputs[1].value, "");
+        assert_eq!(form.inputs[2].value, "");
+    }
+
+    #[test]
+    fn mouse() {
+        use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+        let click = |column, row| {
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+        let mut form = Form::new((80, 24)).unwrap().with_mouse(true);
+        form.add_input(Input::builder((10, 2), 10, "first").build());
+        form.add_input(
+            Input::builder((10, 4), 10, "notes")
+                .with_height(2)
+                .with_value("one two three four five six")
+                .build(),
+        );
+        form.current_pos = (0, 0).into();
+
+        form.event_handler(&click(13, 2)).unwrap();
+        assert_eq!(form.current_pos, (13, 2).into());
+
+        // Labels and empty space are not clickable
+        form.event_handler(&click(40, 10)).unwrap();
+        assert_eq!(form.current_pos, (13, 2).into());
+
+        form.event_handler(&Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 12,
+            row: 5,
+            modifiers: KeyModifiers::NONE,
+        }))
+        .unwrap();
+        assert_eq!(form.inputs[1].scroll, 1);
+
+        // " Esc=Abort ─ Enter=Submit "
+        assert_eq!(
+            form.event_handler(&click(5, 24)).unwrap(),
+            EventHandlerResult::Handled(EventResult::Abort)
+        );
+        assert_eq!(
+            form.event_handler(&click(16, 24)).unwrap(),
+            EventHandlerResult::Handled(EventResult::Submit)
+        );
+        assert_eq!(
+            form.event_handler(&click(13, 24)).unwrap(),
+            EventHandlerResult::Handled(EventResult::None)
+        );
+    }
+
+    #[test]
+    fn help() {
+        let f1 = Event::Key(KeyCode::F(1).into());
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(Input::builder((0, 0), 10, "user").build());
+        form.add_input(Input::builder((0, 2), 10, "other").build());
+        form.add_help("user", "Enter your user id").unwrap();
+        form.add_help("user", "Up to 8 characters").unwrap();
+        form.current_pos = (3, 2).into();
+
+        form.event_handler(&f1).unwrap();
+        assert!(form.help_form.is_none());
+        assert_eq!(form.error_message.as_deref(), Some("No help available"));
+
+        form.set_help("Form help");
+        form.event_handler(&f1).unwrap();
+        assert_eq!(form.help_form.as_ref().unwrap().lines, vec!["Form help"]);
+        form.event_handler(&Event::Key(KeyCode::Esc.into()))
+            .unwrap();
+        assert!(form.help_form.is_none());
+
+        form.current_pos = (3, 0).into();
+        form.event_handler(&f1).unwrap();
+        let help = form.help_form.as_ref().unwrap();
+        assert_eq!(help.title, "Help for user");
+        assert_eq!(help.lines, vec!["Enter your user id", "Up to 8 characters"]);
+
+        // Keys go to the help until it is closed, Esc does not abort the form
+        form.event_handler(&Event::Key(KeyCode::Char('x').into()))
+            .unwrap();
+        assert_eq!(
+            form.event_handler(&Event::Key(KeyCode::Esc.into()))
+                .unwrap(),
+            EventHandlerResult::Handled(EventResult::None)
+        );
+        assert!(form.help_form.is_none());
+        assert_eq!(form.inputs[0].value, "");
+        assert_eq!(form.current_pos, (3, 0).into());
+    }
+
+    #[test]
+    fn protected_and_hidden() {
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(Input::builder((0, 0), 10, "first").build());
+        form.add_input(
+            Input::protected((0, 1), 10, "status")
+                .with_value("Active")
+                .build(),
+        );
+        form.add_input(
+            Input::protected((0, 2), 10, "title")
+                .with_value("Screen")
+                .with_output(false)
+                .build(),
+        );
+        form.add_input(Input::hidden("token").with_value("abc123").build());
+        form.add_input(Input::builder((0, 3), 10, "last").build());
+
+        form.first_input();
+        form.next_input();
+        assert_eq!(form.current_pos, (0, 3).into());
+
+        form.current_pos = (0, 1).into();
+        form.event_handler(&Event::Key(KeyCode::Char('x').into()))
+            .unwrap();
+        form.event_handler(&Event::Paste("x".into())).unwrap();
+        form.event_handler(&Event::Key(KeyEvent::new(KeyCode::End, KeyModifiers::ALT)))
+            .unwrap();
+        assert_eq!(form.inputs[1].value, "Active");
+        assert_eq!(form.inputs[3].value, "abc123");
+
+        // Hidden fields can not be reached, even at their position
+        form.current_pos = (0, 0).into();
+        assert_eq!(form.focused(), Some(0));
+
+        assert!(form.set_value("status", "Locked"));
+        assert_eq!(
+            form.get_field_and_data(),
+            vec![
+                ("first", "".to_string()),
+                ("status", "Locked".to_string()),
+                ("token", "abc123".to_string()),
+                ("last", "".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn newline_keymap() {
+        let mut form = Form::new((80, 24)).unwrap().with_keymap(KeyMap::newline());
+        form.add_input(Input::builder((0, 0), 10, "first").build());
+        form.add_input(Input::builder((0, 2), 10, "second").build());
+        form.current_pos = (0, 0).into();
+
+        assert_eq!(
+            form.event_handler(&Event::Key(KeyCode::Enter.into()))
+                .unwrap(),
+            EventHandlerResult::Handled(EventResult::None)
+        );
+        assert_eq!(form.current_pos, (0, 2).into());
+
+        assert_eq!(
+            form.event_handler(&Event::Key(KeyEvent::new(
+                KeyCode::Enter,
+                KeyModifiers::CONTROL
+            )))
+            .unwrap(),
+            EventHandlerResult::Handled(EventResult::Submit)
+        );
+    }
+
+    #[test]
+    fn newline_keymap_text_area() {
+        let mut form = Form::new((80, 24)).unwrap().with_keymap(KeyMap::newline());
+        form.add_input(
+            Input::builder((0, 0), 10, "notes")
+                .with_height(3)
+                .with_value("ab")
+                .build(),
+        );
+        let mut form = form.place_cursor();
+        form.current_pos = (2, 0).into();
+
+        // Alt-Enter starts a new line, Ctrl-J submits as Ctrl-Enter does
+        assert_eq!(
+            form.event_handler(&Event::Key(KeyEvent::new(
+                KeyCode::Enter,
+                KeyModifiers::ALT
+            )))
+            .unwrap(),
+            EventHandlerResult::Handled(EventResult::None)
+        );
+        assert_eq!(form.inputs[0].value, "ab\n");
+
+        assert_eq!(
+            form.event_handler(&Event::Key(KeyEvent::new(
+                KeyCode::Char('j'),
+                KeyModifiers::CONTROL
+            )))
+            .unwrap(),
+            EventHandlerResult::Handled(EventResult::Submit)
+        );
+        assert_eq!(form.inputs[0].value, "ab\n");
+
+        // Without the binding Ctrl-J is a new line
+        let mut form = form.with_keymap(KeyMap::default());
+        form.event_handler(&Event::Key(KeyEvent::new(
+            KeyCode::Char('j'),
+            KeyModifiers::CONTROL,
+        )))
+        .unwrap();
+        assert_eq!(form.inputs[0].value, "ab\n\n");
+    }
+
+    #[test]
+    fn tab_order() {
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(Input::builder((0, 0), 10, "a").build());
+        form.add_input(Input::builder((0, 2), 10, "b").with_tab_index(2).build());
+        form.add_input(Input::builder((0, 4), 10, "c").with_tab_index(1).build());
+        form.add_input(Input::builder((0, 6), 10, "d").with_tab_stop(false).build());
+        form.add_input(Input::builder((0, 8), 10, "e").build());
+        let mut form = form.place_cursor();
+
+        assert_eq!(form.current_pos, (0, 4).into());
+
+        let mut visited = Vec::new();
+        for _ in 0..5 {
+            form.next_input();
+            visited.push(form.current_pos.y);
+        }
+        assert_eq!(visited, vec![2, 0, 8, 4, 2]);
+
+        form.prev_input();
+        assert_eq!(form.current_pos, (0, 4).into());
+        form.prev_input();
+        assert_eq!(form.current_pos, (0, 8).into());
+
+        // Off the tab order, the next tab stop on screen
+        form.current_pos = (0, 6).into();
+        form.next_input();
+        assert_eq!(form.current_pos, (0, 8).into());
+
+        form.inputs[4].initial_cursor = true;
+        let form = form.place_cursor();
+        assert_eq!(form.current_pos, (0, 8).into());
+    }
+
+    #[test]
+    fn place_cursor_first_input() {
+        // The first input is focused even at 0,0, where it used to be skipped
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(Input::builder((0, 0), 10, "name").build());
+        form.add_input(Input::builder((0, 2), 6, "count").build());
+        let mut form = form.place_cursor();
+
+        assert_eq!(form.current_pos, (0, 0).into());
+        assert_eq!(form.current_field().map(|i| i.name.as_str()), Some("name"));
+    }
+
+    #[test]
+    fn viewport() {
+        let mut form = Form::new((82, 60)).unwrap().with_viewport((82, 24));
+        form.add_label(Label::new_label((0, 2), "Top"));
+        form.add_input(Input::builder((10, 2), 10, "top").build());
+        form.add_input(Input::builder((10, 50), 10, "bottom").build());
+        let mut form = form.place_cursor();
+
+        form.scroll_to_cursor();
+        assert_eq!(form.offset, (0, 0).into());
+        assert_eq!(form.in_view(&form.inputs[1]), None);
+
+        // 23 rows are shown above the status row
+        form.next_input();
+        form.scroll_to_cursor();
+        assert_eq!(form.offset, (0, 28).into());
+        assert_eq!(form.in_view(&form.inputs[0]), None);
+        assert_eq!(form.in_view(&form.inputs[1]), Some((10, 22).into()));
+        assert_eq!(form.clip((0, 2).into(), "Top"), None);
+
+        // Never scrolled past the end of the form
+        form.current_pos = (0, 59).into();
+        form.scroll_to_cursor();
+        assert_eq!(form.offset, (0, 36).into());
+
+        form.current_pos = (0, 2).into();
+        form.scroll_to_cursor();
+        assert_eq!(form.offset, (0, 2).into());
+        assert_eq!(
+            form.clip((0, 2).into(), "Top"),
+            Some(((0, 0).into(), "Top".to_string()))
+        );
+
+        // Mouse clicks are in screen coordinates
+        form.mouse = true;
+        form.event_handler(&Event::Mouse(crossterm::event::MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 12,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        }))
+        .unwrap();
+        assert_eq!(form.current_pos, (12, 2).into());
+    }
+
+    #[test]
+    fn popups_fit_viewport() {
+        let mut form = Form::new((82, 24)).unwrap();
+        form.add_input(Input::builder((0, 0), 10, "a").build());
+        form.new_page();
+        form.add_input(Input::builder((0, 0), 10, "b").build());
+        form.set_help("Form help");
+        let mut form = form.place_cursor();
+        assert_eq!(
+            form.page_indicator(),
+            Some((65, " Page 1 of 2 ".to_string()))
+        );
+
+        form.fit_terminal(50, 16);
+        assert_eq!(form.popup_size(), (49, 15).into());
+        assert_eq!(
+            form.page_indicator(),
+            Some((34, " Page 1 of 2 ".to_string()))
+        );
+
+        form.event_handler(&Event::Key(KeyCode::F(1).into()))
+            .unwrap();
+        assert_eq!(form.help_form.as_ref().unwrap().size, (49, 15).into());
+    }
+
+    #[test]
+    fn viewport_columns() {
+        let mut form = Form::new((82, 24)).unwrap();
+        form.fit_terminal(60, 40);
+        assert_eq!(form.viewport, (61, 24).into());
+
+        // Labels are cut at the edges of the viewport
+        form.current_pos = (70, 5).into();
+        form.scroll_to_cursor();
+        assert_eq!(form.offset, (12, 0).into());
+        assert_eq!(
+            form.clip((10, 5).into(), "Long label text"),
+            Some(((0, 5).into(), "ng label text".to_string()))
+        );
+        assert_eq!(
+            form.clip((68, 5).into(), "Long label text"),
+            Some(((56, 5).into(), "Lon".to_string()))
+        );
+    }
+
+    #[test]
+    fn group() {
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(Input::builder((0, 0), 10, "order").build());
+        form.begin_group(
+            Group::builder("item", 3)
+                .with_visible(2)
+                .with_max_rows(4)
+                .build(),
+        )
+        .unwrap();
+        form.add_input(Input::builder((0, 2), 4, "qty").build());
+        form.add_input(Input::builder((6, 2), 10, "part").build());
+        form.end_group().unwrap();
+        form.add_input(Input::builder((0, 5), 10, "note").build());
+        form.set_hint("qty", "Quantity").unwrap();
+        let mut form = form.place_cursor();
+
+        let names: Vec<&str> = form
+            .get_field_and_data()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "order",
+                "item[0].qty",
+                "item[0].part",
+                "item[1].qty",
+                "item[1].part",
+                "item[2].qty",
+                "item[2].part",
+                "note"
+            ]
+        );
+        assert!(form.inputs[5].hint.is_some());
+
+        // Tab scrolls the third row into view
+        for _ in 0..5 {
+            form.next_input();
+        }
+        assert_eq!(form.current_pos, (0, 3).into());
+        assert_eq!(form.current_field().unwrap().name, "item[2].qty");
+        assert!(form.inputs[1].scrolled_out);
+
+        form.set_value("item[0].qty", "1");
+        form.set_value("item[1].qty", "2");
+        form.set_value("item[2].qty", "3");
+
+        let alt = |code| Event::Key(KeyEvent::new(code, KeyModifiers::ALT));
+        form.focus_row(0, 1, 0);
+        form.event_handler(&alt(KeyCode::Insert)).unwrap();
+        assert_eq!(form.current_field().unwrap().name, "item[2].qty");
+        assert_eq!(
+            form.get_json(),
+            concat!(
+                r#"{"order":"","item":[{"qty":"1","part":""},{"qty":"2","part":""},"#,
+                r#"{"qty":"","part":""},{"qty":"3","part":""}],"note":""}"#
+            )
+        );
+        assert!(!form.undo());
+
+        // Full
+        assert!(!form.insert_row());
+        assert!(form.error_message.is_some());
+
+        form.event_handler(&alt(KeyCode::Delete)).unwrap();
+        assert_eq!(form.current_field().unwrap().value, "3");
+        assert_eq!(form.groups[0].rows, 3);
+        assert_eq!(form.get_field_and_data().len(), 8);
+
+        // Moving up off the top row scrolls the group
+        form.focus_row(0, 1, 0);
+        assert_eq!(form.current_pos, (0, 2).into());
+        form.event_handler(&Event::Key(KeyCode::Up.into())).unwrap();
+        assert_eq!(form.current_pos, (0, 2).into());
+        assert_eq!(form.current_field().unwrap().name, "item[0].qty");
+    }
+
+    #[test]
+    fn undo_group_row() {
+        let ctrl = |c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+        let key = |code| Event::Key(KeyEvent::from(code));
+        let mut form = Form::new((80, 24)).unwrap();
+        form.begin_group(Group::builder("item", 3).with_visible(1).build())
+            .unwrap();
+        form.add_input(Input::builder((0, 2), 4, "qty").build());
+        form.end_group().unwrap();
+        let mut form = form.place_cursor();
+
+        form.event_handler(&key(KeyCode::Char('7'))).unwrap();
+        form.event_handler(&key(KeyCode::Down)).unwrap();
+        form.event_handler(&key(KeyCode::Down)).unwrap();
+        assert_eq!(form.groups[0].scroll, 2);
+
+        // The first row is scrolled back into view
+        form.event_handler(&ctrl('z')).unwrap();
+        assert_eq!(form.inputs[0].value, "");
+        assert_eq!(form.groups[0].scroll, 0);
+        assert_eq!(form.current_field().unwrap().name, "item[0].qty");
+        assert_eq!(form.current_pos, (0, 2).into());
+
+        form.event_handler(&ctrl('y')).unwrap();
+        assert_eq!(form.inputs[0].value, "7");
+        assert_eq!(form.current_pos, (1, 2).into());
+    }
+
+    #[test]
+    fn json() {
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(
+            Input::builder((0, 0), 10, "name")
+                .with_value("say \"hi\"\n")
+                .build(),
+        );
+
+        assert_eq!(form.get_json(), r#"{"name":"say \"hi\"\n"}"#);
+    }
+
+    #[test]
+    fn pages() {
+        let mut form = Form::new((80, 24)).unwrap();
+        form.add_input(Input::builder((0, 0), 10, "a").build());
+        form.add_input(Input::builder((0, 2), 10, "b").build());
+        form.new_page();
+        form.add_label(Label::new_label((0, 0), "Page two"));
+        form.add_input(Input::builder((0, 4), 10, "c").build());
+        let mut form = form.place_cursor();
+
+        assert_eq!(form.pages(), 2);
+        assert_eq!(form.labels[0].page, 1);
+        assert_eq!((form.page(), form.current_pos), (0, (0, 0).into()));
+
+        // Tab wraps across pages
+        form.next_input();
+        form.next_input();
+        assert_eq!((form.page(), form.current_pos), (1, (0, 4).into()));
+        form.next_input();
+        assert_eq!((form.page(), form.current_pos), (0, (0, 0).into()));
+        form.prev_input();
+        assert_eq!((form.page(), form.current_pos), (1, (0, 4).into()));
+
+        // Fields on other pages can not be reached with the cursor
+        form.current_pos = (0, 0).into();
+        assert_eq!(form.focused(), None);
+
+        form.event_handler(&Event::Key(KeyCode::F(7).into()))
+            .unwrap();
+        assert_eq!((form.page(), form.current_pos), (0, (0, 0).into()));
+        assert!(!form.prev_page());
+
+        form.event_handler(&Event::Key(KeyCode::PageDown.into()))
+            .unwrap();
+        assert_eq!((form.page(), form.current_pos), (1, (0, 4).into()));
+        assert!(!form.next_page());
+
+        // Values of all pages are output
+        form.set_value("a", "one");
+        form.set_value("c", "three");
+        assert_eq!(
+            form.get_field_and_data(),
+            vec![
+                ("a", "one".to_string()),
+                ("b", "".to_string()),
+                ("c", "three".to_string())
+            ]
+        );
+    }
+}
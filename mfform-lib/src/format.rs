@@ -0,0 +1,417 @@
+use chrono::{Local, NaiveDate, NaiveTime};
+
+use crate::mask::Mask;
+
+/// Date layouts supported by DATE fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateFormat {
+    /// YYYY-MM-DD
+    Iso,
+    /// DD.MM.YYYY
+    European,
+    /// YYDDD, two digit year and day of the year
+    Julian,
+}
+
+impl DateFormat {
+    fn template(&self) -> &'static str {
+        match self {
+            DateFormat::Iso => "9999-99-99",
+            DateFormat::European => "99.99.9999",
+            DateFormat::Julian => "99999",
+        }
+    }
+
+    fn strftime(&self) -> &'static str {
+        match self {
+            DateFormat::Iso => "%Y-%m-%d",
+            DateFormat::European => "%d.%m.%Y",
+            DateFormat::Julian => "%y%j",
+        }
+    }
+}
+
+/// Time layouts supported by TIME fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeFormat {
+    /// HH:MM
+    HourMinute,
+    /// HH:MM:SS
+    HourMinuteSecond,
+}
+
+impl TimeFormat {
+    fn template(&self) -> &'static str {
+        match self {
+            TimeFormat::HourMinute => "99:99",
+            TimeFormat::HourMinuteSecond => "99:99:99",
+        }
+    }
+
+    fn strftime(&self) -> &'static str {
+        match self {
+            TimeFormat::HourMinute => "%H:%M",
+            TimeFormat::HourMinuteSecond => "%H:%M:%S",
+        }
+    }
+}
+
+/// Layout and limits of NUMBER fields.
+///
+/// Numbers are edited left justified without separators, and right justified
+/// with the fill character when the cursor leaves the field.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NumberFormat {
+    /// Accept a leading '-' or '+'
+    pub signed: bool,
+    /// Number of decimal places, the value is padded with zeros to match
+    pub decimals: u8,
+    /// Group thousands with ',' when displayed
+    pub thousands: bool,
+    /// Fill with zeros rather than spaces when right justified
+    pub zero_fill: bool,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// Parsed number, `int` without leading zeros and `frac` padded to the
+/// number of decimals
+struct Number {
+    negative: bool,
+    int: String,
+    frac: String,
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.int)?;
+        if !self.frac.is_empty() {
+            write!(f, ".{}", self.frac)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl NumberFormat {
+    /// Parse an edited or displayed value, empty values are None
+    fn parse(&self, value: &str) -> Result<Option<Number>, String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(None);
+        }
+
+        let (negative, unsigned) = match value.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, value.strip_prefix('+').unwrap_or(value)),
+        };
+        if unsigned.len() != value.len() && !self.signed {
+            return Err(format!("{} must not be signed", value));
+        }
+
+        let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        // Separators are only accepted between groups of three digits
+        if int.contains(',') {
+            let mut groups = int.split(',');
+            let head = groups.next().unwrap_or_default();
+            if !self.thousands || !(1..=3).contains(&head.len()) || groups.any(|g| g.len() != 3) {
+                return Err(format!("{} is not a valid number", value));
+            }
+        }
+        let int: String = int.chars().filter(|c| *c != ',').collect();
+        if (int.is_empty() && frac.is_empty())
+            || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit())
+        {
+            return Err(format!("{} is not a valid number", value));
+        }
+        if frac.len() > self.decimals as usize {
+            return Err(format!(
+                "{} has more than {} decimals",
+                value, self.decimals
+            ));
+        }
+
+        let int = match int.trim_start_matches('0') {
+            "" => "0".to_string(),
+            int => int.to_string(),
+        };
+        let frac = format!("{:0<width$}", frac, width = self.decimals as usize);
+        let zero = int == "0" && frac.chars().all(|c| c == '0');
+
+        let number = Number {
+            negative: negative && !zero,
+            int,
+            frac,
+        };
+
+        let n: f64 = number.to_string().parse().unwrap_or_default();
+        if let Some(min) = self.min.filter(|min| n < *min) {
+            return Err(format!("{} is below the minimum of {}", value, min));
+        }
+        if let Some(max) = self.max.filter(|max| n > *max) {
+            return Err(format!("{} is above the maximum of {}", value, max));
+        }
+
+        Ok(Some(number))
+    }
+
+    /// Characters that can be typed into the field
+    fn allowed_characters(&self) -> Vec<char> {
+        let mut allowed: Vec<char> = ('0'..='9').collect();
+
+        if self.signed {
+            allowed.extend(['-', '+']);
+        }
+        if self.decimals > 0 {
+            allowed.push('.');
+        }
+        if self.thousands {
+            allowed.push(',');
+        }
+
+        allowed
+    }
+
+    /// Value as edited, without fill and separators.  Invalid values are
+    /// returned as is.
+    pub(crate) fn edit(&self, value: &str) -> String {
+        match self.parse(value) {
+            Ok(Some(number)) => number.to_string(),
+            Ok(None) => String::new(),
+            Err(_) => value.to_string(),
+        }
+    }
+
+    /// Value right justified to `length`, invalid values and values too long
+    /// for the field are returned as edited.
+    pub(crate) fn justify(&self, value: &str, length: usize) -> String {
+        let Ok(Some(number)) = self.parse(value) else {
+            return self.edit(value);
+        };
+
+        // Groups are counted from the right, the first one may be short
+        let int = if self.thousands {
+            let (head, tail) = number.int.split_at(number.int.len() % 3);
+            let groups = tail
+                .as_bytes()
+                .chunks(3)
+                .map(|c| String::from_utf8_lossy(c).into_owned());
+
+            std::iter::once(head.to_string())
+                .filter(|h| !h.is_empty())
+                .chain(groups)
+                .collect::<Vec<_>>()
+                .join(",")
+        } else {
+            number.int.clone()
+        };
+
+        let sign = if number.negative { "-" } else { "" };
+        let body = if number.frac.is_empty() {
+            int
+        } else {
+            format!("{}.{}", int, number.frac)
+        };
+
+        let width = sign.len() + body.len();
+        if width > length {
+            return number.to_string();
+        }
+
+        if self.zero_fill {
+            format!("{}{}{}", sign, "0".repeat(length - width), body)
+        } else {
+            format!("{:>length$}", format!("{}{}", sign, body))
+        }
+    }
+}
+
+/// Structured field value, defining the edit mask, validation and how the
+/// value is output.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Format {
+    /// Calendar date, output as YYYY-MM-DD
+    Date(DateFormat),
+    /// Time of day, output as HH:MM:SS
+    Time(TimeFormat),
+    /// Number, output without fill and separators
+    Number(NumberFormat),
+}
+
+impl Format {
+    /// Edit mask of the format, numbers are not masked
+    pub fn mask(&self) -> Option<Mask> {
+        match self {
+            Format::Date(d) => Some(Mask::from_template(d.template())),
+            Format::Time(t) => Some(Mask::from_template(t.template())),
+            Format::Number(_) => None,
+        }
+    }
+
+    /// Characters that can be typed into the field
+    pub fn allowed_characters(&self) -> Vec<char> {
+        match self {
+            Format::Date(_) | Format::Time(_) => ('0'..='9').collect(),
+            Format::Number(n) => n.allowed_characters(),
+        }
+    }
+
+    /// Check an edited value
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        match self {
+            Format::Date(d) => NaiveDate::parse_from_str(value, d.strftime())
+                .map(|_| ())
+                .map_err(|_| format!("{} is not a valid date", value.trim())),
+            Format::Time(t) => NaiveTime::parse_from_str(value, t.strftime())
+                .map(|_| ())
+                .map_err(|_| format!("{} is not a valid time", value.trim())),
+            Format::Number(n) => n.parse(value).map(|_| ()),
+        }
+    }
+
+    /// Normalised value for output, invalid values are returned as is
+    pub fn output(&self, value: &str) -> String {
+        match self {
+            Format::Number(n) => return n.edit(value),
+            Format::Date(d) => NaiveDate::parse_from_str(value, d.strftime())
+                .map(|d| d.format("%Y-%m-%d").to_string()),
+            Format::Time(t) => NaiveTime::parse_from_str(value, t.strftime())
+                .map(|t| t.format("%H:%M:%S").to_string()),
+        }
+        .unwrap_or_else(|_| value.to_string())
+    }
+
+    /// Current date or time, in the layout of the field
+    pub fn now(&self) -> Option<String> {
+        let now = Local::now();
+
+        match self {
+            Format::Date(d) => Some(now.format(d.strftime()).to_string()),
+            Format::Time(t) => Some(now.format(t.strftime()).to_string()),
+            Format::Number(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_dates() {
+        assert!(Format::Date(DateFormat::Iso).validate("2024-02-29").is_ok());
+        assert!(Format::Date(DateFormat::Iso)
+            .validate("2023-02-29")
+            .is_err());
+        assert!(Format::Date(DateFormat::European)
+            .validate("31.12.1999")
+            .is_ok());
+        assert!(Format::Date(DateFormat::European)
+            .validate("31.04.1999")
+            .is_err());
+        assert!(Format::Date(DateFormat::Julian).validate("24366").is_ok());
+        assert!(Format::Date(DateFormat::Julian).validate("23366").is_err());
+    }
+
+    #[test]
+    fn validate_times() {
+        assert!(Format::Time(TimeFormat::HourMinute)
+            .validate("23:59")
+            .is_ok());
+        assert!(Format::Time(TimeFormat::HourMinute)
+            .validate("24:00")
+            .is_err());
+    }
+
+    #[test]
+    fn normalised_output() {
+        assert_eq!(
+            Format::Date(DateFormat::European).output("01.02.2024"),
+            "2024-02-01"
+        );
+        assert_eq!(
+            Format::Date(DateFormat::Julian).output("24032"),
+            "2024-02-01"
+        );
+        assert_eq!(
+            Format::Time(TimeFormat::HourMinute).output("07:30"),
+            "07:30:00"
+        );
+    }
+
+    #[test]
+    fn now_fits_mask() {
+        for format in [
+            Format::Date(DateFormat::Iso),
+            Format::Date(DateFormat::European),
+            Format::Date(DateFormat::Julian),
+            Format::Time(TimeFormat::HourMinuteSecond),
+        ] {
+            let now = format.now().unwrap();
+
+            assert_eq!(now.chars().count(), format.mask().unwrap().len());
+            assert!(format.validate(&now).is_ok());
+        }
+    }
+
+    #[test]
+    fn validate_numbers() {
+        let amount = Format::Number(NumberFormat {
+            signed: true,
+            decimals: 2,
+            thousands: true,
+            min: Some(-1000.0),
+            max: Some(1_000_000.0),
+            ..Default::default()
+        });
+
+        assert!(amount.validate("").is_ok());
+        assert!(amount.validate("-12.5").is_ok());
+        assert!(amount.validate("  1,234.50").is_ok());
+        assert!(amount.validate("1.234").is_err());
+        assert!(amount.validate("1,2,3").is_err());
+        assert!(amount.validate("12,34").is_err());
+        assert!(amount.validate(",123").is_err());
+        assert!(amount.validate("1234,567").is_err());
+        assert!(amount.validate("1-2").is_err());
+        assert!(amount.validate(".").is_err());
+        assert!(amount.validate("-1000.01").is_err());
+        assert!(amount.validate("1000000.01").is_err());
+
+        let count = Format::Number(NumberFormat::default());
+        assert!(count.validate("42").is_ok());
+        assert!(count.validate("-42").is_err());
+        assert!(count.validate("4.2").is_err());
+        assert!(count.validate("1,234").is_err());
+    }
+
+    #[test]
+    fn justify_numbers() {
+        let amount = NumberFormat {
+            signed: true,
+            decimals: 2,
+            thousands: true,
+            ..Default::default()
+        };
+
+        assert_eq!(amount.justify("-1234567.5", 14), " -1,234,567.50");
+        assert_eq!(amount.justify("123", 8), "  123.00");
+        assert_eq!(amount.justify("-0", 6), "  0.00");
+        assert_eq!(amount.justify("1234567", 8), "1234567.00");
+        assert_eq!(amount.justify("abc", 8), "abc");
+        assert_eq!(amount.edit(" -1,234,567.50"), "-1234567.50");
+
+        let zero = NumberFormat {
+            signed: true,
+            zero_fill: true,
+            ..Default::default()
+        };
+
+        assert_eq!(zero.justify("-0042", 6), "-00042");
+        assert_eq!(zero.justify("", 6), "");
+        assert_eq!(Format::Number(zero).output("+000"), "0");
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{input::Input, pos::Pos};
+
+/// Input on a row of a repeating group, with the index of the field in the
+/// row template it was made from
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct GroupRow {
+    pub group: usize,
+    pub row: usize,
+    pub field: usize,
+}
+
+/// Repeating group of fields, e.g. line items of an order.  The fields of
+/// the first row make up a template repeated on the rows below, named
+/// `name[row].field`.  Rows can be inserted up to the maximum number of rows,
+/// the group scrolls when it has more rows than are shown.
+#[derive(Debug, Clone)]
+pub struct Group {
+    pub name: String,
+    /// Number of rows
+    pub rows: usize,
+    /// Rows shown, the group scrolls when it has more rows
+    pub visible: usize,
+    /// Rows can be inserted up to this number
+    pub max_rows: usize,
+    /// Fields of the first row
+    pub(crate) fields: Vec<Input>,
+    /// First row shown
+    pub(crate) scroll: usize,
+    pub(crate) page: usize,
+}
+
+impl Group {
+    /// Create a GroupBuilder for a group starting out with `rows` rows, all
+    /// of them shown.  A group always has at least one row.
+    pub fn builder(name: impl Into<String>, rows: usize) -> GroupBuilder {
+        GroupBuilder {
+            name: name.into(),
+            rows: rows.max(1),
+            visible: None,
+            max_rows: None,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Name of a field on a row
+    pub(crate) fn field_name(&self, row: usize, field: usize) -> String {
+        format!("{}[{}].{}", self.name, row, self.fields[field].name)
+    }
+
+    /// First screen row of the group and the height of each row
+    pub(crate) fn row_span(&self) -> (u16, u16) {
+        let top = self.fields.iter().map(|f| f.pos.y).min().unwrap_or(0);
+        let bottom = self
+            .fields
+            .iter()
+            .map(|f| f.pos.y + f.height)
+            .max()
+            .unwrap_or(top + 1);
+
+        (top, (bottom - top).max(1))
+    }
+
+    /// Position of a field on a row in view
+    pub(crate) fn field_pos(&self, row: usize, field: usize) -> Option<Pos> {
+        let (_, height) = self.row_span();
+        let template = self.fields.get(field)?;
+
+        self.in_view(row).then(|| Pos {
+            x: template.pos.x,
+            y: template.pos.y + (row - self.scroll) as u16 * height,
+        })
+    }
+
+    pub(crate) fn in_view(&self, row: usize) -> bool {
+        row >= self.scroll && row < self.scroll + self.visible
+    }
+
+    /// Scroll just enough to bring a row into view
+    pub(crate) fn scroll_to(&mut self, row: usize) {
+        if row < self.scroll {
+            self.scroll = row;
+        } else if row >= self.scroll + self.visible {
+            self.scroll = row + 1 - self.visible;
+        }
+        self.scroll = self.scroll.min(self.rows.saturating_sub(self.visible));
+    }
+
+    /// Column just right of the widest field
+    pub(crate) fn right(&self) -> u16 {
+        self.fields
+            .iter()
+            .map(|f| f.pos.x + f.length)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+pub struct GroupBuilder {
+    pub name: String,
+    pub rows: usize,
+    pub visible: Option<usize>,
+    pub max_rows: Option<usize>,
+    pub fields: Vec<Input>,
+}
+
+impl GroupBuilder {
+    /// Rows shown, the group scrolls when it has more rows
+    pub fn with_visible(mut self, visible: usize) -> Self {
+        self.visible = Some(visible.max(1));
+
+        self
+    }
+
+    /// Allow rows to be inserted up to this number
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows);
+
+        self
+    }
+
+    /// Add a field to the row template, positioned on the first row
+    pub fn with_field(mut self, input: Input) -> Self {
+        self.fields.push(input);
+
+        self
+    }
+
+    pub fn build(self) -> Group {
+        Group {
+            name: self.name,
+            rows: self.rows,
+            visible: self.visible.unwrap_or(self.rows),
+            max_rows: self.max_rows.unwrap_or(self.rows).max(self.rows),
+            fields: self.fields,
+            scroll: 0,
+            page: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows() {
+        let mut group = Group::builder("item", 5)
+            .with_visible(2)
+            .with_field(Input::builder((2, 6), 5, "qty").build())
+            .with_field(Input::builder((8, 6), 10, "part").build())
+            .build();
+
+        assert_eq!(group.max_rows, 5);
+        assert_eq!(group.row_span(), (6, 1));
+        assert_eq!(group.field_name(3, 0), "item[3].qty");
+        assert_eq!(group.field_pos(1, 1), Some((8, 7).into()));
+        assert_eq!(group.field_pos(2, 1), None);
+
+        group.scroll_to(3);
+        assert_eq!(group.scroll, 2);
+        assert_eq!(group.field_pos(3, 0), Some((2, 7).into()));
+
+        group.scroll_to(9);
+        assert_eq!(group.scroll, 3);
+        group.scroll_to(0);
+        assert_eq!(group.scroll, 0);
+    }
+}
@@ -0,0 +1,203 @@
+use crossterm::{
+    cursor,
+    event::{Event, KeyCode, MouseEventKind},
+    style,
+    terminal::{self, ClearType},
+    QueueableCommand,
+};
+use std::io::{self, Stdout, Write};
+
+use crate::{
+    app::{EventHandlerResult, EventResult},
+    pos::Pos,
+    shape, text_area,
+    theme::Theme,
+};
+
+/// First row of help text
+const TOP: u16 = 2;
+
+/// Help text popup, scrolled with the arrow keys, page keys or mouse wheel.
+/// Esc returns to the form.
+#[derive(Debug, Clone)]
+pub struct HelpForm {
+    pub(crate) title: String,
+    pub(crate) lines: Vec<String>,
+    pub(crate) scroll: usize,
+    pub(crate) size: Pos,
+    pub(crate) theme: Theme,
+    pub(crate) ascii: bool,
+}
+
+impl HelpForm {
+    /// Help text is word wrapped to the width of the popup
+    pub fn new(title: impl Into<String>, text: &str, size: impl Into<Pos>) -> Self {
+        let size = size.into();
+        let text = text.trim_end();
+        let width = size.x.saturating_sub(6) as usize;
+        let chars: Vec<char> = text.chars().collect();
+
+        let lines = text_area::wrap(text, width)
+            .into_iter()
+            .map(|row| chars[row.start..row.start + row.len].iter().collect())
+            .collect();
+
+        Self {
+            title: title.into(),
+            lines,
+            scroll: 0,
+            size,
+            theme: Theme::from_env(),
+            ascii: false,
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+
+        self
+    }
+
+    /// Draw the border with plain ASCII
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+
+        self
+    }
+
+    pub fn display(&mut self, stdout: &mut Stdout) -> io::Result<()> {
+        // Clear dialog
+        stdout
+            .queue(cursor::MoveTo(self.size.x, self.size.y))?
+            .queue(terminal::Clear(ClearType::FromCursorUp))?;
+
+        // Border
+        let (vertical, horizontal, corner) = shape::border_chars(self.ascii);
+        stdout.queue(style::SetForegroundColor(self.theme.border))?;
+        for y in 0..self.size.y {
+            stdout
+                .queue(cursor::MoveTo(self.size.x, y))?
+                .queue(style::Print(vertical))?;
+        }
+        stdout
+            .queue(cursor::MoveTo(0, self.size.y))?
+            .queue(style::Print(
+                horizontal.to_string().repeat(self.size.x as usize),
+            ))?
+            .queue(style::Print(corner))?
+            .queue(cursor::MoveTo(2, self.size.y))?
+            .queue(style::Print(" Esc=Return "))?;
+
+        let more = match (self.scroll > 0, self.scroll < self.max_scroll()) {
+            (false, false) => None,
+            (true, false) => Some("More: -"),
+            (false, true) => Some("More:  +"),
+            (true, true) => Some("More: -+"),
+        };
+        if let Some(more) = more {
+            stdout
+                .queue(cursor::MoveTo(self.size.x.saturating_sub(10), 0))?
+                .queue(style::Print(more))?;
+        }
+
+        stdout
+            .queue(cursor::MoveTo(2, 0))?
+            .queue(style::SetForegroundColor(self.theme.label))?
+            .queue(style::SetAttribute(style::Attribute::Bold))?
+            .queue(style::Print(&self.title))?
+            .queue(style::SetAttribute(style::Attribute::Reset))?
+            .queue(style::SetForegroundColor(self.theme.label))?;
+
+        for (row, line) in self
+            .lines
+            .iter()
+            .skip(self.scroll)
+            .take(self.height())
+            .enumerate()
+        {
+            stdout
+                .queue(cursor::MoveTo(3, TOP + row as u16))?
+                .queue(style::Print(line))?;
+        }
+
+        stdout.queue(cursor::MoveTo(2, 0))?;
+
+        stdout.flush()
+    }
+
+    /// Number of rows of help text shown, between the title and the bottom
+    /// border
+    fn height(&self) -> usize {
+        self.size.y.saturating_sub(TOP + 2) as usize
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(self.height())
+    }
+
+    fn scroll_by(&mut self, rows: isize) {
+        self.scroll = self
+            .scroll
+            .saturating_add_signed(rows)
+            .min(self.max_scroll());
+    }
+
+    /// Esc, Enter and F1 close the help, any other key is swallowed
+    pub fn event_handler(&mut self, event: &Event) -> io::Result<EventHandlerResult> {
+        match event {
+            Event::Key(k) => match k.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::F(1) => {
+                    return Ok(EventHandlerResult::Handled(EventResult::Abort));
+                }
+                KeyCode::Up => self.scroll_by(-1),
+                KeyCode::Down => self.scroll_by(1),
+                KeyCode::PageUp => self.scroll_by(-(self.height() as isize)),
+                KeyCode::PageDown => self.scroll_by(self.height() as isize),
+                KeyCode::Home => self.scroll = 0,
+                KeyCode::End => self.scroll = self.max_scroll(),
+                _ => (),
+            },
+            Event::Mouse(m) if m.kind == MouseEventKind::ScrollUp => self.scroll_by(-1),
+            Event::Mouse(m) if m.kind == MouseEventKind::ScrollDown => self.scroll_by(1),
+            _ => return Ok(EventHandlerResult::NotHandled),
+        }
+
+        Ok(EventHandlerResult::Handled(EventResult::None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scroll() {
+        let text = (1..=30)
+            .map(|i| format!("Line {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut help = HelpForm::new("Help", &text, (80, 24));
+        assert_eq!(help.lines.len(), 30);
+
+        help.event_handler(&Event::Key(KeyCode::PageDown.into()))
+            .unwrap();
+        assert_eq!(help.scroll, 10);
+
+        help.event_handler(&Event::Key(KeyCode::Up.into())).unwrap();
+        assert_eq!(help.scroll, 9);
+
+        assert_eq!(
+            help.event_handler(&Event::Key(KeyCode::Esc.into()))
+                .unwrap(),
+            EventHandlerResult::Handled(EventResult::Abort)
+        );
+    }
+
+    #[test]
+    fn wrap() {
+        let help = HelpForm::new("Help", &"word ".repeat(30), (80, 24));
+
+        assert_eq!(help.lines.len(), 2);
+        assert!(help.lines.iter().all(|l| l.chars().count() <= 74));
+    }
+}
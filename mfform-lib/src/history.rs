@@ -0,0 +1,138 @@
+use std::collections::VecDeque;
+
+use crate::pos::Pos;
+
+/// Cursor position, relative to the input it was on so it can be restored
+/// after the input has scrolled with its group or the page has changed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Cursor {
+    pub input: Option<usize>,
+    pub pos: Pos,
+}
+
+/// Field values changed by a single event
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Edit {
+    /// Input index, value before and value after the edit
+    pub changes: Vec<(usize, String, String)>,
+    pub cursor_before: Cursor,
+    pub cursor_after: Cursor,
+}
+
+/// Bounded undo and redo history of a form, the oldest edits are dropped
+/// when the history is full.
+#[derive(Debug, Clone)]
+pub(crate) struct History {
+    undo: VecDeque<Edit>,
+    redo: Vec<Edit>,
+    limit: usize,
+}
+
+impl History {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit,
+        }
+    }
+
+    /// Record the difference between `before` and `after`, new edits clear
+    /// the redo history.
+    pub fn record(
+        &mut self,
+        before: &[String],
+        after: &[String],
+        cursor_before: Cursor,
+        cursor_after: Cursor,
+    ) {
+        let changes: Vec<_> = before
+            .iter()
+            .zip(after)
+            .enumerate()
+            .filter(|(_, (b, a))| b != a)
+            .map(|(i, (b, a))| (i, b.clone(), a.clone()))
+            .collect();
+
+        if changes.is_empty() {
+            return;
+        }
+
+        self.redo.clear();
+        self.undo.push_back(Edit {
+            changes,
+            cursor_before,
+            cursor_after,
+        });
+        while self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Forget all edits, when inputs are added or removed
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Take the last edit for undoing, it is moved to the redo history
+    pub fn undo(&mut self) -> Option<Edit> {
+        let edit = self.undo.pop_back()?;
+        self.redo.push(edit.clone());
+
+        Some(edit)
+    }
+
+    /// Take the last undone edit for redoing, it is moved back to the undo
+    /// history
+    pub fn redo(&mut self) -> Option<Edit> {
+        let edit = self.redo.pop()?;
+        self.undo.push_back(edit.clone());
+
+        Some(edit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn undo_redo() {
+        let pos = Cursor {
+            input: None,
+            pos: Pos { x: 0, y: 0 },
+        };
+        let mut history = History::new(2);
+
+        history.record(&values(&["a", "b"]), &values(&["a", "b"]), pos, pos);
+        assert_eq!(history.undo(), None);
+
+        history.record(&values(&["a", "b"]), &values(&["ab", "b"]), pos, pos);
+        history.record(&values(&["ab", "b"]), &values(&["ab", ""]), pos, pos);
+        history.record(&values(&["ab", ""]), &values(&["", ""]), pos, pos);
+
+        assert_eq!(
+            history.undo().unwrap().changes,
+            vec![(0, "ab".into(), "".into())]
+        );
+        assert_eq!(
+            history.undo().unwrap().changes,
+            vec![(1, "b".into(), "".into())]
+        );
+        // Oldest edit was dropped
+        assert_eq!(history.undo(), None);
+
+        assert_eq!(
+            history.redo().unwrap().changes,
+            vec![(1, "b".into(), "".into())]
+        );
+
+        history.record(&values(&["ab", ""]), &values(&["abc", ""]), pos, pos);
+        assert_eq!(history.redo(), None);
+    }
+}
@@ -1,11 +1,17 @@
-use std::io;
+use std::{borrow::Cow, io};
 
-use crossterm::event::{Event, KeyCode};
+use crossterm::event::{Event, KeyCode, KeyModifiers};
 use log::debug;
 
 use crate::{
     app::{EventHandlerResult, EventResult},
+    attribute::{Color, Highlight},
+    format::Format,
+    group::GroupRow,
+    mask::Mask,
     pos::Pos,
+    text_area,
+    transform::Transform,
 };
 
 #[allow(dead_code)]
@@ -16,21 +22,80 @@ pub enum Select {
     Multi,
 }
 
+/// Kind of input field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputKind {
+    /// Free text entry
+    Text,
+    /// Check box toggled with Space, the value is "true" or "false"
+    Checkbox,
+    /// Radio button, all buttons with the same name form a group and the
+    /// value of the group is the id of the selected button
+    Radio { id: String },
+}
+
 /// Generic input field, supporting masked input (password) and number fields.
 ///
 /// Also supports 'select'able fields, where the user can press F4 to get a list
 /// of predefined values.
+///
+/// Fields with a height of more than one row are text areas, the value is
+/// word wrapped to the field length and scrolls vertically.  Single row fields
+/// with a maximum length above the field length scroll horizontally.
 #[derive(Debug, Clone)]
 pub struct Input {
     pub pos: Pos,
     pub length: u16,
+    pub height: u16,
+    pub max_length: Option<u16>,
     pub name: String,
+    pub kind: InputKind,
     pub value: String,
     pub default_value: String,
     pub allowed_characters: Option<Vec<char>>,
     pub mask_char: Option<char>,
     pub select: Select,
     pub select_static: Vec<(String, String)>,
+    pub color: Option<Color>,
+    pub highlight: Option<Highlight>,
+    pub mask: Option<Mask>,
+    /// Output masked values without the mask literals
+    pub raw_output: bool,
+    pub format: Option<Format>,
+    /// Applied in order as characters are typed and when the value is output
+    pub transforms: Vec<Transform>,
+    /// Move to the next field when the last position is filled, None follows
+    /// the form setting
+    pub autoskip: Option<bool>,
+    /// Position in the tab order, fields without an index follow the ones
+    /// with an index in screen order
+    pub tab_index: Option<u16>,
+    /// Reached with Tab and BackTab
+    pub tab_stop: bool,
+    /// Place the cursor on this field when the form is shown
+    pub initial_cursor: bool,
+    /// Help text shown with F1
+    pub help: Option<String>,
+    /// One line hint shown on the status row while the cursor is on the field
+    pub hint: Option<String>,
+    /// Shown in place of an empty value, never part of the value
+    pub placeholder: Option<String>,
+    /// Output field, displayed but not editable.  The value can still be
+    /// changed through [`Form::set_value`](crate::Form::set_value).
+    pub protected: bool,
+    /// Never displayed, the value is carried through to the output
+    pub hidden: bool,
+    /// Included in [`Form::get_field_and_data`](crate::Form::get_field_and_data)
+    pub output: bool,
+    /// Page of a multi-page form, set when the input is added to the form
+    pub(crate) page: usize,
+    /// Row of a repeating group the input belongs to
+    pub(crate) group_row: Option<GroupRow>,
+    /// On a row of a repeating group scrolled out of view
+    pub(crate) scrolled_out: bool,
+    /// First wrapped row displayed in a text area, or first char displayed in
+    /// a horizontally scrolling field
+    pub(crate) scroll: usize,
 }
 
 impl Ord for Input {
@@ -55,7 +120,19 @@ impl PartialOrd for Input {
 
 impl Input {
     pub(crate) fn has_focus(&self, cursor: Pos) -> bool {
-        cursor.within(self.pos, self.length).is_some()
+        if self.hidden || self.scrolled_out {
+            false
+        } else if self.is_text_area() {
+            cursor
+                .within_area(self.pos, self.length, self.height)
+                .is_some()
+        } else {
+            cursor.within(self.pos, self.length).is_some()
+        }
+    }
+
+    pub(crate) fn is_text_area(&self) -> bool {
+        self.height > 1
     }
 
     /// Create a InputBuilder for creating a new Input field.
@@ -63,13 +140,85 @@ impl Input {
         InputBuilder {
             pos: pos.into(),
             length,
+            height: 1,
+            max_length: None,
             name: name.into(),
+            kind: InputKind::Text,
             value: Default::default(),
             default_value: Default::default(),
             allowed_characters: Default::default(),
             mask_char: Default::default(),
             select: Select::None,
             select_static: Default::default(),
+            color: None,
+            highlight: None,
+            mask: None,
+            raw_output: false,
+            format: None,
+            transforms: Vec::new(),
+            autoskip: None,
+            tab_index: None,
+            tab_stop: true,
+            initial_cursor: false,
+            help: None,
+            hint: None,
+            placeholder: None,
+            protected: false,
+            hidden: false,
+            output: true,
+        }
+    }
+
+    /// Create a InputBuilder for a check box, unchecked unless told otherwise.
+    pub fn checkbox(pos: impl Into<Pos>, name: impl Into<String>) -> InputBuilder {
+        let mut builder = Self::builder(pos, 3, name);
+        builder.kind = InputKind::Checkbox;
+
+        builder.with_checked(false)
+    }
+
+    /// Create a InputBuilder for a radio button with the given id, belonging
+    /// to the group `name`.
+    pub fn radio(
+        pos: impl Into<Pos>,
+        name: impl Into<String>,
+        id: impl Into<String>,
+    ) -> InputBuilder {
+        let mut builder = Self::builder(pos, 3, name);
+        builder.kind = InputKind::Radio { id: id.into() };
+
+        builder
+    }
+
+    /// Create a InputBuilder for a protected output field, left out of the
+    /// tab order.
+    pub fn protected(pos: impl Into<Pos>, length: u16, name: impl Into<String>) -> InputBuilder {
+        Self::builder(pos, length, name)
+            .with_protected(true)
+            .with_tab_stop(false)
+    }
+
+    /// Create a InputBuilder for a hidden field, carrying a value through to
+    /// the output.
+    pub fn hidden(name: impl Into<String>) -> InputBuilder {
+        let mut builder = Self::builder((0, 0), 0, name).with_tab_stop(false);
+        builder.hidden = true;
+
+        builder
+    }
+
+    /// Protected and hidden fields are not changed by typing, erasing or
+    /// resetting
+    pub(crate) fn is_protected(&self) -> bool {
+        self.protected || self.hidden
+    }
+
+    /// Is the check box checked, or the radio button selected
+    pub fn is_checked(&self) -> bool {
+        match &self.kind {
+            InputKind::Text => false,
+            InputKind::Checkbox => self.value == "true",
+            InputKind::Radio { id } => self.value == *id,
         }
     }
 
@@ -78,23 +227,47 @@ impl Input {
         event: &Event,
         current_pos: &mut Pos,
     ) -> std::io::Result<EventHandlerResult> {
+        if self.is_text_area() {
+            return self.text_area_event_handler(event, current_pos);
+        }
+
+        if self.kind != InputKind::Text {
+            return self.toggle_event_handler(event);
+        }
+
         match event {
+            Event::Key(k)
+                if k.code == KeyCode::Char('t')
+                    && k.modifiers == KeyModifiers::CONTROL
+                    && self.format.is_some() =>
+            {
+                self.key_now(current_pos);
+            }
             Event::Key(k) if k.code == KeyCode::Backspace => {
                 self.key_backspace(current_pos)?;
             }
-            Event::Key(k) if k.code == KeyCode::Delete => {
+            // Alt-Delete deletes a row of a repeating group
+            Event::Key(k)
+                if k.code == KeyCode::Delete && !k.modifiers.contains(KeyModifiers::ALT) =>
+            {
                 self.key_delete(current_pos)?;
             }
-            Event::Key(k) if k.modifiers.is_empty() => {
+            Event::Key(k) if k.code == KeyCode::End && k.modifiers == KeyModifiers::CONTROL => {
+                self.key_erase_eof(current_pos);
+            }
+            Event::Key(k) if k.code == KeyCode::End && k.modifiers.is_empty() => {
+                self.key_end(current_pos);
+            }
+            Event::Key(k) if k.modifiers.difference(KeyModifiers::SHIFT).is_empty() => {
                 if let KeyCode::Char(c) = k.code {
-                    if let Some(ac) = &self.allowed_characters {
-                        if !ac.contains(&c) {
-                            debug!("{} is not an allowed character for input {}", c, self.name);
-                            return Ok(EventHandlerResult::Handled(EventResult::None));
-                        }
+                    let c = self.transform_key(c);
+                    if !self.is_allowed(c) {
+                        return Ok(EventHandlerResult::Handled(EventResult::None));
                     }
 
-                    self.key(c, current_pos);
+                    if self.key(c, current_pos) {
+                        return Ok(EventHandlerResult::Filled);
+                    }
                 } else {
                     return Ok(EventHandlerResult::NotHandled);
                 }
@@ -105,11 +278,270 @@ impl Input {
         Ok(EventHandlerResult::Handled(EventResult::None))
     }
 
-    pub(crate) fn key(&mut self, key: char, current_pos: &mut Pos) {
-        let str_pos = current_pos.x - self.pos.x;
-        *current_pos = current_pos.move_x(1, self.pos.x + self.length);
+    /// Check boxes and radio buttons only react to Space, other characters
+    /// are swallowed.
+    fn toggle_event_handler(&mut self, event: &Event) -> io::Result<EventHandlerResult> {
+        match event {
+            Event::Key(k) if k.code == KeyCode::Char(' ') => match &self.kind {
+                InputKind::Checkbox => {
+                    self.value = (!self.is_checked()).to_string();
+                }
+                InputKind::Radio { id } => {
+                    self.value = id.to_string();
+                }
+                InputKind::Text => unreachable!(),
+            },
+            Event::Key(k)
+                if matches!(k.code, KeyCode::Char(_) | KeyCode::Backspace)
+                    || (k.code == KeyCode::Delete && !k.modifiers.contains(KeyModifiers::ALT)) => {}
+            _ => return Ok(EventHandlerResult::NotHandled),
+        }
+
+        Ok(EventHandlerResult::Handled(EventResult::None))
+    }
+
+    fn transform_key(&self, c: char) -> char {
+        self.transforms.iter().fold(c, |c, t| t.key(c))
+    }
+
+    fn is_allowed(&self, c: char) -> bool {
+        match &self.allowed_characters {
+            Some(ac) if !ac.contains(&c) => {
+                debug!("{} is not an allowed character for input {}", c, self.name);
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// Number of characters the value can hold
+    pub(crate) fn capacity(&self) -> usize {
+        self.max_length.unwrap_or(self.length).max(self.length) as usize
+    }
+
+    /// Char index into the value under the cursor
+    pub(crate) fn index(&self, current_pos: Pos) -> usize {
+        self.scroll + (current_pos.x - self.pos.x) as usize
+    }
+
+    /// Place the cursor on a char index, scrolling the field if needed
+    pub(crate) fn place_cursor(&mut self, index: usize, current_pos: &mut Pos) {
+        let length = self.length as usize;
+        if length == 0 {
+            // Nothing is shown, the cursor stays at the start of the field
+            current_pos.x = self.pos.x;
+            return;
+        }
+        let index = index.min(self.capacity().saturating_sub(1));
+
+        if index < self.scroll {
+            self.scroll = index;
+        } else if index >= self.scroll + length {
+            self.scroll = index + 1 - length;
+        }
+
+        current_pos.x = self.pos.x + (index - self.scroll) as u16;
+    }
+
+    /// Type a character at the cursor, returns true when the last position
+    /// of the field was filled.
+    pub(crate) fn key(&mut self, key: char, current_pos: &mut Pos) -> bool {
+        let index = self.index(*current_pos);
+
+        if let Some(mask) = &self.mask {
+            // Skip over literals to the next input position
+            let Some(index) = mask.next_input(index) else {
+                debug!("Field {} is full", self.name);
+                return false;
+            };
+
+            if !mask.0[index].accepts(key) {
+                debug!("{} is not accepted at {} in {}", key, index, self.name);
+                return false;
+            }
+
+            let next = mask.next_input(index + 1);
+            self.value = Self::set_char_in_string(&self.edited_value(), index, key);
+            self.place_cursor(next.unwrap_or(index + 1), current_pos);
+            return next.is_none();
+        }
+
+        if index >= self.capacity() {
+            debug!("Field {} is full", self.name);
+            return false;
+        }
+
+        self.value = Self::set_char_in_string(&self.value, index, key);
+        self.place_cursor(index + 1, current_pos);
+        index + 1 == self.capacity()
+    }
+
+    /// Paste text at the cursor.  Characters are entered as if typed, so
+    /// length, masks, transforms and allowed characters apply.  Single line
+    /// fields take the first line only, tabs become spaces.  Check boxes and
+    /// radio buttons ignore pastes.
+    pub(crate) fn paste(&mut self, text: &str, current_pos: &mut Pos) {
+        if self.kind != InputKind::Text || self.is_protected() {
+            return;
+        }
+
+        if self.is_text_area() {
+            for c in text.chars().filter(|c| *c != '\r') {
+                let c = self.transform_key(c);
+                if c == '\n' || (!c.is_control() && self.is_allowed(c)) {
+                    let index = self.text_area_index(*current_pos);
+                    self.text_area_insert(c, index, current_pos);
+                }
+            }
+            return;
+        }
+
+        for c in text.chars().take_while(|c| !matches!(c, '\r' | '\n')) {
+            let c = self.transform_key(if c == '\t' { ' ' } else { c });
+            if c.is_control() || !self.is_allowed(c) {
+                continue;
+            }
+
+            if self.key(c, current_pos) {
+                break;
+            }
+        }
+    }
+
+    /// Fill in the current date or time
+    fn key_now(&mut self, current_pos: &mut Pos) {
+        if let Some(now) = self.format.as_ref().and_then(Format::now) {
+            self.value = now;
+            self.place_cursor(0, current_pos);
+        }
+    }
+
+    /// Erase EOF, clear from the cursor to the end of the field
+    fn key_erase_eof(&mut self, current_pos: &Pos) {
+        let index = self.index(*current_pos);
+
+        self.erase_range(index, self.value.chars().count());
+    }
+
+    /// Remove the characters from `start` up to `end`, masked fields keep
+    /// their structure and the input positions are blanked instead.
+    fn erase_range(&mut self, start: usize, end: usize) {
+        self.value = match &self.mask {
+            Some(mask) => (start..end.min(mask.len()))
+                .filter(|i| !mask.0[*i].is_literal())
+                .fold(self.edited_value().to_string(), |value, i| {
+                    Self::set_char_in_string(&value, i, ' ')
+                }),
+            None => (start..end).fold(self.value.clone(), |value, _| {
+                Self::delete_in_string(&value, start)
+            }),
+        };
+    }
+
+    /// Move the cursor just past the last non blank character
+    fn key_end(&mut self, current_pos: &mut Pos) {
+        let end = match &self.mask {
+            Some(mask) => {
+                let end = self
+                    .edited_value()
+                    .chars()
+                    .zip(mask.0.iter())
+                    .enumerate()
+                    .filter(|(_, (c, m))| !m.is_literal() && *c != ' ')
+                    .last()
+                    .map(|(i, _)| i + 1)
+                    .unwrap_or_default();
+
+                mask.next_input(end).unwrap_or(end)
+            }
+            None => self.value.trim_end().chars().count(),
+        };
+
+        self.place_cursor(end, current_pos);
+    }
+
+    /// Erase Input, clear the value.  Check boxes are unchecked and radio
+    /// buttons deselected.
+    pub(crate) fn clear(&mut self) {
+        self.value = match self.kind {
+            InputKind::Checkbox => false.to_string(),
+            InputKind::Text | InputKind::Radio { .. } => String::new(),
+        };
+        self.scroll = 0;
+    }
+
+    /// Reset Field, restore the default value
+    pub(crate) fn reset(&mut self) {
+        self.value = self.default_value.clone();
+        self.scroll = 0;
+    }
+
+    /// The cursor moved into the field, numbers are edited without fill and
+    /// separators
+    pub(crate) fn enter(&mut self) {
+        if let Some(Format::Number(number)) = &self.format {
+            self.value = number.edit(&self.value);
+            self.scroll = 0;
+        }
+    }
+
+    /// The cursor left the field, numbers are right justified
+    pub(crate) fn leave(&mut self) {
+        if let Some(Format::Number(number)) = &self.format {
+            self.value = number.justify(&self.value, self.length as usize);
+            self.scroll = 0;
+        }
+    }
+
+    /// The value as displayed, masked fields show the mask literals even
+    /// when nothing has been entered.
+    pub(crate) fn edited_value(&self) -> Cow<'_, str> {
+        match &self.mask {
+            Some(mask) if self.value.is_empty() => Cow::Owned(mask.blank()),
+            _ => Cow::Borrowed(&self.value),
+        }
+    }
 
-        self.value = Self::set_char_in_string(&self.value, str_pos as usize, key);
+    /// Nothing entered, masked fields may hold just the mask literals
+    pub(crate) fn is_blank(&self) -> bool {
+        match &self.mask {
+            Some(mask) => mask.is_blank(&self.value),
+            None => self.value.is_empty(),
+        }
+    }
+
+    /// The value as output, normalised according to the field format and
+    /// transformed
+    pub fn output_value(&self) -> String {
+        if let Some(mask) = &self.mask {
+            if mask.is_blank(&self.value) {
+                return String::new();
+            }
+        }
+
+        let value = match (&self.format, &self.mask) {
+            (Some(format), _) => format.output(&self.value),
+            (None, Some(mask)) if self.raw_output => mask.raw(&self.value),
+            _ => self.value.clone(),
+        };
+
+        self.transforms
+            .iter()
+            .fold(value, |value, t| t.output(&value))
+    }
+
+    /// Check the value against the field format, empty fields are valid
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.format, &self.mask) {
+            (_, Some(mask)) if mask.is_blank(&self.value) => Ok(()),
+            (Some(format), _) => format
+                .validate(&self.value)
+                .map_err(|e| format!("{}: {}", self.name, e)),
+            (None, Some(mask)) if !mask.is_complete(&self.value) => {
+                Err(format!("{}: Incomplete value", self.name))
+            }
+            _ => Ok(()),
+        }
     }
 
     pub(crate) fn set_char_in_string(s: &str, pos: usize, ch: char) -> String {
@@ -130,6 +562,15 @@ impl Input {
         output
     }
 
+    pub(crate) fn insert_in_string(s: &str, pos: usize, ch: char) -> String {
+        let mut output: String = s.chars().take(pos).collect();
+
+        output.push(ch);
+        output.extend(s.chars().skip(pos));
+
+        output
+    }
+
     pub(crate) fn delete_in_string(input: &str, pos: usize) -> String {
         let input_len = input.chars().count();
         if pos > input_len {
@@ -143,37 +584,286 @@ impl Input {
     }
 
     pub(crate) fn key_backspace(&mut self, current_pos: &mut Pos) -> io::Result<()> {
-        let str_pos = current_pos.x - self.pos.x;
+        let index = self.index(*current_pos);
 
-        // No backspace at start of field
-        if str_pos == 0 {
+        // Masked fields keep their structure, the previous position is blanked
+        if let Some(mask) = &self.mask {
+            if let Some(index) = mask.prev_input(index) {
+                self.value = Self::set_char_in_string(&self.edited_value(), index, ' ');
+                self.place_cursor(index, current_pos);
+            }
             return Ok(());
         }
 
-        *current_pos = current_pos.move_x(-1, self.pos.x + self.length);
+        // No backspace at start of field
+        if index == 0 {
+            return Ok(());
+        }
 
-        self.value = Self::delete_in_string(&self.value, (str_pos - 1) as usize);
+        self.value = Self::delete_in_string(&self.value, index - 1);
+        self.place_cursor(index - 1, current_pos);
         Ok(())
     }
 
     fn key_delete(&mut self, current_pos: &Pos) -> io::Result<()> {
-        let str_pos = current_pos.x - self.pos.x;
+        let index = self.index(*current_pos);
+
+        if let Some(mask) = &self.mask {
+            if mask.0.get(index).is_some_and(|m| !m.is_literal()) {
+                self.value = Self::set_char_in_string(&self.edited_value(), index, ' ');
+            }
+            return Ok(());
+        }
 
-        self.value = Self::delete_in_string(&self.value, str_pos as usize);
+        self.value = Self::delete_in_string(&self.value, index);
         Ok(())
     }
 }
 
+/// Text area handling, characters are inserted rather than overwritten and
+/// the cursor follows the text as it is re-wrapped.
+impl Input {
+    fn text_area_event_handler(
+        &mut self,
+        event: &Event,
+        current_pos: &mut Pos,
+    ) -> io::Result<EventHandlerResult> {
+        let Event::Key(k) = event else {
+            return Ok(EventHandlerResult::NotHandled);
+        };
+
+        let index = self.text_area_index(*current_pos);
+
+        match k.code {
+            KeyCode::Backspace => {
+                if index > 0 {
+                    self.value = Self::delete_in_string(&self.value, index - 1);
+                    self.text_area_cursor(index - 1, current_pos);
+                }
+            }
+            KeyCode::Delete if !k.modifiers.contains(KeyModifiers::ALT) => {
+                self.value = Self::delete_in_string(&self.value, index);
+                self.text_area_cursor(index, current_pos);
+            }
+            // Enter submits the form, new lines are entered with Alt-Enter or
+            // Ctrl-J unless Ctrl-J is bound to Submit
+            KeyCode::Enter if k.modifiers.contains(KeyModifiers::ALT) => {
+                self.text_area_insert('\n', index, current_pos);
+            }
+            KeyCode::Char('j') if k.modifiers == KeyModifiers::CONTROL => {
+                self.text_area_insert('\n', index, current_pos);
+            }
+            KeyCode::Char(c) if k.modifiers.difference(KeyModifiers::SHIFT).is_empty() => {
+                let c = self.transform_key(c);
+                if self.is_allowed(c) {
+                    self.text_area_insert(c, index, current_pos);
+                }
+            }
+            KeyCode::End if k.modifiers == KeyModifiers::CONTROL => {
+                self.value = self.value.chars().take(index).collect();
+            }
+            KeyCode::End => {
+                self.text_area_cursor(self.value.chars().count(), current_pos);
+            }
+            KeyCode::Up if current_pos.y == self.pos.y && self.scroll > 0 => {
+                self.scroll -= 1;
+            }
+            KeyCode::Down
+                if current_pos.y == self.pos.y + self.height - 1
+                    && self.scroll + (self.height as usize) < self.text_area_rows().len() =>
+            {
+                self.scroll += 1;
+            }
+            _ => return Ok(EventHandlerResult::NotHandled),
+        }
+
+        Ok(EventHandlerResult::Handled(EventResult::None))
+    }
+
+    /// Scroll a text area one row, within the rows of the value
+    pub(crate) fn scroll_rows(&mut self, down: bool) {
+        if down {
+            let rows = self.text_area_rows().len();
+            if self.scroll + (self.height as usize) < rows {
+                self.scroll += 1;
+            }
+        } else {
+            self.scroll = self.scroll.saturating_sub(1);
+        }
+    }
+
+    pub(crate) fn text_area_rows(&self) -> Vec<text_area::Row> {
+        text_area::wrap(&self.value, self.length as usize)
+    }
+
+    /// Char index into the value for a cursor position within the text area
+    fn text_area_index(&self, current_pos: Pos) -> usize {
+        let row = (current_pos.y - self.pos.y) as usize + self.scroll;
+        let col = (current_pos.x - self.pos.x) as usize;
+
+        text_area::index(&self.text_area_rows(), self.value.chars().count(), row, col)
+    }
+
+    /// Place the cursor on a char index, scrolling it into view
+    fn text_area_cursor(&mut self, index: usize, current_pos: &mut Pos) {
+        let (row, col) = text_area::position(&self.text_area_rows(), index);
+        let height = self.height as usize;
+
+        if row < self.scroll {
+            self.scroll = row;
+        } else if row >= self.scroll + height {
+            self.scroll = row + 1 - height;
+        }
+
+        *current_pos = Pos {
+            x: self.pos.x + col as u16,
+            y: self.pos.y + (row - self.scroll) as u16,
+        };
+    }
+
+    fn text_area_insert(&mut self, ch: char, index: usize, current_pos: &mut Pos) {
+        self.value = Self::insert_in_string(&self.value, index, ch);
+        self.text_area_cursor(index + 1, current_pos);
+    }
+}
+
+/// Readline style editing, enabled per form.  Words are runs of
+/// alphanumeric characters, except for Ctrl-W which erases back to
+/// whitespace like a shell does.
+impl Input {
+    pub(crate) fn readline_event_handler(
+        &mut self,
+        event: &Event,
+        current_pos: &mut Pos,
+    ) -> io::Result<EventHandlerResult> {
+        let Event::Key(k) = event else {
+            return Ok(EventHandlerResult::NotHandled);
+        };
+
+        if self.kind != InputKind::Text {
+            return Ok(EventHandlerResult::NotHandled);
+        }
+
+        let chars: Vec<char> = self.edited_value().chars().collect();
+        let index = self.cursor_index(*current_pos);
+        let alphanumeric = |c: char| c.is_alphanumeric();
+
+        match (k.modifiers, k.code) {
+            (KeyModifiers::CONTROL, KeyCode::Char('a')) => {
+                self.move_cursor(0, current_pos);
+            }
+            (KeyModifiers::CONTROL, KeyCode::Char('e')) if self.is_text_area() => {
+                self.move_cursor(chars.len(), current_pos);
+            }
+            (KeyModifiers::CONTROL, KeyCode::Char('e')) => {
+                self.key_end(current_pos);
+            }
+            (KeyModifiers::CONTROL, KeyCode::Char('k')) => {
+                self.erase_range(index, chars.len());
+                self.move_cursor(index, current_pos);
+            }
+            (KeyModifiers::CONTROL, KeyCode::Char('u')) => {
+                self.erase_range(0, index);
+                self.move_cursor(0, current_pos);
+            }
+            (KeyModifiers::CONTROL, KeyCode::Char('w')) => {
+                let start = word_start(&chars, index, |c| !c.is_whitespace());
+                self.erase_range(start, index);
+                self.move_cursor(start, current_pos);
+            }
+            (KeyModifiers::ALT, KeyCode::Char('b')) => {
+                self.move_cursor(word_start(&chars, index, alphanumeric), current_pos);
+            }
+            (KeyModifiers::ALT, KeyCode::Char('f')) => {
+                self.move_cursor(word_end(&chars, index, alphanumeric), current_pos);
+            }
+            _ => return Ok(EventHandlerResult::NotHandled),
+        }
+
+        Ok(EventHandlerResult::Handled(EventResult::None))
+    }
+
+    /// Char index under the cursor, for both single row fields and text areas
+    fn cursor_index(&self, current_pos: Pos) -> usize {
+        if self.is_text_area() {
+            self.text_area_index(current_pos)
+        } else {
+            self.index(current_pos)
+        }
+    }
+
+    /// Place the cursor on a char index, skipping mask literals
+    fn move_cursor(&mut self, index: usize, current_pos: &mut Pos) {
+        let index = match &self.mask {
+            Some(mask) => mask.next_input(index).unwrap_or(index),
+            None => index,
+        };
+
+        if self.is_text_area() {
+            self.text_area_cursor(index, current_pos);
+        } else {
+            self.place_cursor(index, current_pos);
+        }
+    }
+}
+
+/// Start of the word before `index`
+fn word_start(chars: &[char], index: usize, is_word: impl Fn(char) -> bool) -> usize {
+    let mut i = index.min(chars.len());
+
+    while i > 0 && !is_word(chars[i - 1]) {
+        i -= 1;
+    }
+    while i > 0 && is_word(chars[i - 1]) {
+        i -= 1;
+    }
+
+    i
+}
+
+/// End of the word at or after `index`
+fn word_end(chars: &[char], index: usize, is_word: impl Fn(char) -> bool) -> usize {
+    let mut i = index.min(chars.len());
+
+    while i < chars.len() && !is_word(chars[i]) {
+        i += 1;
+    }
+    while i < chars.len() && is_word(chars[i]) {
+        i += 1;
+    }
+
+    i
+}
+
 pub struct InputBuilder {
     pub pos: Pos,
     pub length: u16,
+    pub height: u16,
+    pub max_length: Option<u16>,
     pub name: String,
+    pub kind: InputKind,
     pub value: String,
     pub default_value: String,
     pub allowed_characters: Option<Vec<char>>,
     pub mask_char: Option<char>,
     pub select: Select,
     pub select_static: Vec<(String, String)>,
+    pub color: Option<Color>,
+    pub highlight: Option<Highlight>,
+    pub mask: Option<Mask>,
+    pub raw_output: bool,
+    pub format: Option<Format>,
+    pub transforms: Vec<Transform>,
+    pub autoskip: Option<bool>,
+    pub tab_index: Option<u16>,
+    pub tab_stop: bool,
+    pub initial_cursor: bool,
+    pub help: Option<String>,
+    pub hint: Option<String>,
+    pub placeholder: Option<String>,
+    pub protected: bool,
+    pub hidden: bool,
+    pub output: bool,
 }
 
 impl InputBuilder {
@@ -204,6 +894,148 @@ impl InputBuilder {
         self
     }
 
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+
+        self
+    }
+
+    pub fn with_highlight(mut self, highlight: Highlight) -> Self {
+        self.highlight = Some(highlight);
+
+        self
+    }
+
+    /// Number of rows, anything above one makes the input a text area
+    pub fn with_height(mut self, height: u16) -> Self {
+        self.height = height.max(1);
+
+        self
+    }
+
+    /// Check or uncheck a check box, or select a radio button.  This is also
+    /// the state the field is reset to.
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        match &self.kind {
+            InputKind::Text => return self,
+            InputKind::Checkbox => self.value = checked.to_string(),
+            InputKind::Radio { id } if checked => self.value = id.to_string(),
+            InputKind::Radio { .. } => self.value = String::new(),
+        }
+        self.default_value = self.value.clone();
+
+        self
+    }
+
+    /// Edit mask, e.g. from a picture clause.  Sets the field length to
+    /// match the mask.
+    pub fn with_mask(mut self, mask: Mask) -> Self {
+        self.length = mask.len() as u16;
+        self.mask = Some(mask);
+
+        self
+    }
+
+    /// Output the value without the mask literals
+    pub fn with_raw_output(mut self, raw_output: bool) -> Self {
+        self.raw_output = raw_output;
+
+        self
+    }
+
+    /// Structured value, e.g. a date.  Sets the edit mask and the field
+    /// length to match the format, numbers keep the field length.
+    pub fn with_format(mut self, format: Format) -> Self {
+        if let Some(mask) = format.mask() {
+            self.length = mask.len() as u16;
+            self.mask = Some(mask);
+        }
+        self.allowed_characters = Some(format.allowed_characters());
+        self.format = Some(format);
+
+        self
+    }
+
+    /// Add a transform, applied after any transforms added earlier
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transforms.push(transform);
+
+        self
+    }
+
+    /// Move to the next field when the last position is filled, overriding
+    /// the form setting
+    pub fn with_autoskip(mut self, autoskip: bool) -> Self {
+        self.autoskip = Some(autoskip);
+
+        self
+    }
+
+    /// Position in the tab order
+    pub fn with_tab_index(mut self, tab_index: u16) -> Self {
+        self.tab_index = Some(tab_index);
+
+        self
+    }
+
+    /// Leave the field out of the tab order when false, the field can still
+    /// be reached with the arrow keys
+    pub fn with_tab_stop(mut self, tab_stop: bool) -> Self {
+        self.tab_stop = tab_stop;
+
+        self
+    }
+
+    /// Place the cursor on this field when the form is shown
+    pub fn with_initial_cursor(mut self, initial_cursor: bool) -> Self {
+        self.initial_cursor = initial_cursor;
+
+        self
+    }
+
+    /// Help text shown with F1 when the cursor is on the field
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+
+        self
+    }
+
+    /// One line hint shown on the status row while the cursor is on the field
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+
+        self
+    }
+
+    /// Text shown greyed out while the field is empty, e.g. `YYYY-MM-DD`
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = Some(placeholder.into());
+
+        self
+    }
+
+    /// Display the value without allowing it to be edited
+    pub fn with_protected(mut self, protected: bool) -> Self {
+        self.protected = protected;
+
+        self
+    }
+
+    /// Leave the field out of the form output when false
+    pub fn with_output(mut self, output: bool) -> Self {
+        self.output = output;
+
+        self
+    }
+
+    /// Maximum length of the value, if longer than the field length the field
+    /// scrolls horizontally
+    pub fn with_max_length(mut self, max_length: u16) -> Self {
+        self.max_length = Some(max_length);
+
+        self
+    }
+
     #[allow(dead_code)]
     pub fn with_select_static(mut self, select_static: &[(String, String)]) -> Self {
         self.select_static = select_static.into();
@@ -212,17 +1044,49 @@ impl InputBuilder {
     }
 
     pub fn build(self) -> Input {
-        Input {
+        let mut input = Input {
             pos: self.pos,
             length: self.length,
+            height: self.height,
+            max_length: self.max_length,
             name: self.name,
+            kind: self.kind,
             value: self.value,
             default_value: self.default_value,
             allowed_characters: self.allowed_characters,
             mask_char: self.mask_char,
             select: self.select,
             select_static: self.select_static,
+            color: self.color,
+            highlight: self.highlight,
+            mask: self.mask,
+            raw_output: self.raw_output,
+            format: self.format,
+            transforms: self.transforms,
+            autoskip: self.autoskip,
+            tab_index: self.tab_index,
+            tab_stop: self.tab_stop,
+            initial_cursor: self.initial_cursor,
+            help: self.help,
+            hint: self.hint,
+            placeholder: self.placeholder,
+            protected: self.protected,
+            hidden: self.hidden,
+            output: self.output,
+            page: 0,
+            group_row: None,
+            scrolled_out: false,
+            scroll: 0,
+        };
+
+        // Numbers are displayed right justified until the field is entered
+        if let Some(Format::Number(number)) = &input.format {
+            let length = input.length as usize;
+            input.value = number.justify(&input.value, length);
+            input.default_value = number.justify(&input.default_value, length);
         }
+
+        input
     }
 }
 
@@ -253,4 +1117,328 @@ mod tests {
 
         assert_eq!(output, expected);
     }
+
+    #[test]
+    fn insert_in_string_nls() {
+        assert_eq!(Input::insert_in_string("1æ34", 2, 'ö'), "1æö34");
+        assert_eq!(Input::insert_in_string("1æ34", 4, 'ö'), "1æ34ö");
+    }
+
+    #[test]
+    fn text_area_typing_wraps() {
+        let mut input = Input::builder((0, 0), 5, "text").with_height(2).build();
+        let mut pos: Pos = (0, 0).into();
+
+        for c in "abc defg".chars() {
+            input.key_event(c, &mut pos);
+        }
+
+        assert_eq!(input.value, "abc defg");
+        assert_eq!(pos, (4, 1).into());
+    }
+
+    #[test]
+    fn text_area_scrolls() {
+        let mut input = Input::builder((0, 0), 5, "text").with_height(2).build();
+        let mut pos: Pos = (0, 0).into();
+
+        for c in "aaaaa bbbbb ccc".chars() {
+            input.key_event(c, &mut pos);
+        }
+
+        assert_eq!(input.scroll, 1);
+        assert_eq!(pos, (3, 1).into());
+    }
+
+    #[test]
+    fn field_full() {
+        let mut input = Input::builder((10, 0), 3, "short").build();
+        let mut pos: Pos = (10, 0).into();
+
+        for c in "abcd".chars() {
+            input.key_event(c, &mut pos);
+        }
+
+        assert_eq!(input.value, "abd");
+        assert_eq!(pos, (12, 0).into());
+    }
+
+    #[test]
+    fn horizontal_scroll() {
+        let mut input = Input::builder((10, 0), 4, "long")
+            .with_max_length(8)
+            .build();
+        let mut pos: Pos = (10, 0).into();
+
+        for c in "abcdef".chars() {
+            input.key_event(c, &mut pos);
+        }
+
+        assert_eq!(input.value, "abcdef");
+        assert_eq!(input.scroll, 3);
+        assert_eq!(pos, (13, 0).into());
+
+        input.key_backspace(&mut pos).unwrap();
+        input.key_backspace(&mut pos).unwrap();
+        input.key_backspace(&mut pos).unwrap();
+        input.key_backspace(&mut pos).unwrap();
+
+        assert_eq!(input.value, "ab");
+        assert_eq!(input.scroll, 2);
+        assert_eq!(pos, (10, 0).into());
+    }
+
+    #[test]
+    fn zero_length() {
+        let mut input = Input::builder((10, 0), 0, "x").build();
+        let mut pos: Pos = (10, 0).into();
+
+        input.place_cursor(0, &mut pos);
+        assert_eq!(pos, (10, 0).into());
+        assert_eq!(input.scroll, 0);
+
+        let mut input = Input::builder((10, 0), 0, "x").with_max_length(8).build();
+        input.place_cursor(5, &mut pos);
+        assert_eq!(pos, (10, 0).into());
+        assert_eq!(input.scroll, 0);
+    }
+
+    #[test]
+    fn checkbox_toggle() {
+        let mut input = Input::checkbox((0, 0), "agree").build();
+        let mut pos: Pos = (0, 0).into();
+
+        assert_eq!(input.value, "false");
+        input.key_event(' ', &mut pos);
+        assert_eq!(input.value, "true");
+        assert!(input.is_checked());
+        input.key_event('a', &mut pos);
+        input.key_event(' ', &mut pos);
+        assert_eq!(input.value, "false");
+        assert_eq!(pos, (0, 0).into());
+    }
+
+    #[test]
+    fn date_mask() {
+        use crate::format::DateFormat;
+
+        let mut input = Input::builder((0, 0), 0, "date")
+            .with_format(Format::Date(DateFormat::European))
+            .build();
+        let mut pos: Pos = (0, 0).into();
+
+        assert_eq!(input.length, 10);
+        assert_eq!(input.edited_value(), "  .  .    ");
+        assert_eq!(input.output_value(), "");
+
+        for c in "3102".chars() {
+            input.key_event(c, &mut pos);
+        }
+        assert_eq!(input.value, "31.02.    ");
+        assert_eq!(pos, (6, 0).into());
+
+        for c in "2024".chars() {
+            input.key_event(c, &mut pos);
+        }
+        assert!(input.validate().is_err());
+
+        pos = (5, 0).into();
+        input.key_backspace(&mut pos).unwrap();
+        input.key_backspace(&mut pos).unwrap();
+        assert_eq!(input.value, "31.  .2024");
+        assert_eq!(pos, (3, 0).into());
+
+        for c in "01".chars() {
+            input.key_event(c, &mut pos);
+        }
+        assert!(input.validate().is_ok());
+        assert_eq!(input.output_value(), "2024-01-31");
+    }
+
+    #[test]
+    fn picture_mask() {
+        let mut input = Input::builder((0, 0), 0, "ssn")
+            .with_mask(Mask::from_picture("999-99-9999").unwrap())
+            .build();
+        let mut pos: Pos = (0, 0).into();
+
+        for c in "12a3-45678x9".chars() {
+            input.key_event(c, &mut pos);
+        }
+
+        assert_eq!(input.length, 11);
+        assert_eq!(input.value, "123-45-6789");
+        assert_eq!(input.output_value(), "123-45-6789");
+
+        input.raw_output = true;
+        assert_eq!(input.output_value(), "123456789");
+        assert_eq!(input.validate(), Ok(()));
+
+        // Partly filled pictures are rejected, blank ones are fine
+        input.value = "123-45-    ".to_string();
+        assert_eq!(input.validate(), Err("ssn: Incomplete value".to_string()));
+        input.value = "   -  -    ".to_string();
+        assert_eq!(input.validate(), Ok(()));
+    }
+
+    #[test]
+    fn number_justified() {
+        use crate::format::NumberFormat;
+
+        let mut input = Input::builder((0, 0), 8, "amount")
+            .with_format(Format::Number(NumberFormat {
+                signed: true,
+                decimals: 2,
+                ..Default::default()
+            }))
+            .with_value("12.5")
+            .build();
+        let mut pos: Pos = (0, 0).into();
+
+        assert_eq!(input.value, "   12.50");
+
+        input.enter();
+        assert_eq!(input.value, "12.50");
+
+        for c in "-7a.2".chars() {
+            input.key_event(c, &mut pos);
+        }
+        assert_eq!(input.value, "-7.20");
+
+        input.leave();
+        assert_eq!(input.value, "   -7.20");
+        assert_eq!(input.output_value(), "-7.20");
+    }
+
+    #[test]
+    fn transforms() {
+        let mut input = Input::builder((0, 0), 12, "dsname")
+            .with_transform(Transform::Upper)
+            .with_transform(Transform::Trim)
+            .build();
+        let mut pos: Pos = (0, 0).into();
+
+        for c in " sys1.Lib".chars() {
+            input.key_event(c, &mut pos);
+        }
+        let event = Event::Key(crossterm::event::KeyEvent::new(
+            KeyCode::Char('X'),
+            KeyModifiers::SHIFT,
+        ));
+        input.event_handler(&event, &mut pos).unwrap();
+
+        assert_eq!(input.value, " SYS1.LIBX");
+        assert_eq!(input.output_value(), "SYS1.LIBX");
+    }
+
+    #[test]
+    fn blank_with_placeholder() {
+        let mut input = Input::builder((0, 0), 0, "date")
+            .with_format(Format::Date(crate::format::DateFormat::Iso))
+            .with_placeholder("YYYY-MM-DD")
+            .build();
+        assert!(input.is_blank());
+
+        input.value = "    -  -  ".to_string();
+        assert!(input.is_blank());
+        assert_eq!(input.output_value(), "");
+
+        input.value = "2024-  -  ".to_string();
+        assert!(!input.is_blank());
+    }
+
+    #[test]
+    fn paste() {
+        let mut input = Input::builder((0, 0), 0, "ssn")
+            .with_mask(Mask::from_picture("999-99-9999").unwrap())
+            .build();
+        let mut pos: Pos = (0, 0).into();
+
+        input.paste("12a3456789\n0", &mut pos);
+        assert_eq!(input.value, "123-45-6789");
+
+        let mut input = Input::builder((0, 0), 10, "notes").with_height(3).build();
+        let mut pos: Pos = (0, 0).into();
+
+        input.paste("one\r\ntwo", &mut pos);
+        assert_eq!(input.value, "one\ntwo");
+        assert_eq!(pos, (3, 1).into());
+
+        let mut input = Input::checkbox((0, 0), "check").build();
+        input.paste("true", &mut pos);
+        assert!(!input.is_checked());
+    }
+
+    #[test]
+    fn erase_eof_and_end() {
+        let mut input = Input::builder((0, 0), 10, "text")
+            .with_value("abcdef")
+            .build();
+        let mut pos: Pos = (3, 0).into();
+
+        input.key_erase_eof(&pos);
+        assert_eq!(input.value, "abc");
+
+        input.key_end(&mut pos);
+        assert_eq!(pos, (3, 0).into());
+
+        let mut input = Input::builder((0, 0), 0, "ssn")
+            .with_mask(Mask::from_picture("999-99-9999").unwrap())
+            .with_value("123-45-6789")
+            .build();
+        let mut pos: Pos = (5, 0).into();
+
+        input.key_erase_eof(&pos);
+        assert_eq!(input.value, "123-4 -    ");
+
+        pos = (0, 0).into();
+        input.key_end(&mut pos);
+        assert_eq!(pos, (5, 0).into());
+    }
+
+    #[test]
+    fn readline_keys() {
+        let mut input = Input::builder((0, 0), 20, "text")
+            .with_value("þórður og ævar")
+            .build();
+        let mut pos: Pos = (14, 0).into();
+        let key = |code, modifiers| Event::Key(crossterm::event::KeyEvent::new(code, modifiers));
+
+        input
+            .readline_event_handler(&key(KeyCode::Char('b'), KeyModifiers::ALT), &mut pos)
+            .unwrap();
+        assert_eq!(pos, (10, 0).into());
+
+        input
+            .readline_event_handler(&key(KeyCode::Char('w'), KeyModifiers::CONTROL), &mut pos)
+            .unwrap();
+        assert_eq!(input.value, "þórður ævar");
+        assert_eq!(pos, (7, 0).into());
+
+        input
+            .readline_event_handler(&key(KeyCode::Char('a'), KeyModifiers::CONTROL), &mut pos)
+            .unwrap();
+        input
+            .readline_event_handler(&key(KeyCode::Char('f'), KeyModifiers::ALT), &mut pos)
+            .unwrap();
+        assert_eq!(pos, (6, 0).into());
+
+        input
+            .readline_event_handler(&key(KeyCode::Char('k'), KeyModifiers::CONTROL), &mut pos)
+            .unwrap();
+        assert_eq!(input.value, "þórður");
+
+        input
+            .readline_event_handler(&key(KeyCode::Char('u'), KeyModifiers::CONTROL), &mut pos)
+            .unwrap();
+        assert_eq!(input.value, "");
+        assert_eq!(pos, (0, 0).into());
+    }
+
+    impl Input {
+        fn key_event(&mut self, c: char, pos: &mut Pos) {
+            let event = Event::Key(crossterm::event::KeyEvent::from(KeyCode::Char(c)));
+            self.event_handler(&event, pos).unwrap();
+        }
+    }
 }
@@ -0,0 +1,597 @@
+use std::{fmt, io, path::Path, str::FromStr};
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventState, KeyModifiers, ModifierKeyCode};
+
+/// Form level actions keys can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Validate and submit the form
+    Submit,
+    /// Abort the form, popups are closed first
+    Abort,
+    /// Abort the form, even from a popup
+    Quit,
+    /// Show the list of values of the current field
+    Select,
+    /// Show the help of the current field, or of the form
+    Help,
+    NextField,
+    PrevField,
+    FirstField,
+    /// First field on the next row
+    NewLine,
+    /// Clear all fields on the page
+    EraseInput,
+    /// Undo the last change to field values
+    Undo,
+    /// Redo the last undone change
+    Redo,
+    /// Restore the default value of the current field
+    ResetField,
+    /// Restore the default values of all fields
+    ResetForm,
+    /// Next page of a multi-page form
+    NextPage,
+    /// Previous page of a multi-page form
+    PrevPage,
+    /// Insert a row below the current row of a repeating group
+    InsertRow,
+    /// Delete the current row of a repeating group
+    DeleteRow,
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    ToggleDebug,
+}
+
+impl Action {
+    /// Name of the action in key map files
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Submit => "submit",
+            Action::Abort => "abort",
+            Action::Quit => "quit",
+            Action::Select => "select",
+            Action::Help => "help",
+            Action::NextField => "next-field",
+            Action::PrevField => "prev-field",
+            Action::FirstField => "first-field",
+            Action::NewLine => "new-line",
+            Action::EraseInput => "erase-input",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::ResetField => "reset-field",
+            Action::ResetForm => "reset-form",
+            Action::NextPage => "next-page",
+            Action::PrevPage => "prev-page",
+            Action::InsertRow => "insert-row",
+            Action::DeleteRow => "delete-row",
+            Action::MoveLeft => "left",
+            Action::MoveRight => "right",
+            Action::MoveUp => "up",
+            Action::MoveDown => "down",
+            Action::ToggleDebug => "debug",
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Action::Submit,
+            Action::Abort,
+            Action::Quit,
+            Action::Select,
+            Action::Help,
+            Action::NextField,
+            Action::PrevField,
+            Action::FirstField,
+            Action::NewLine,
+            Action::EraseInput,
+            Action::Undo,
+            Action::Redo,
+            Action::ResetField,
+            Action::ResetForm,
+            Action::NextPage,
+            Action::PrevPage,
+            Action::InsertRow,
+            Action::DeleteRow,
+            Action::MoveLeft,
+            Action::MoveRight,
+            Action::MoveUp,
+            Action::MoveDown,
+            Action::ToggleDebug,
+        ]
+        .into_iter()
+        .find(|a| a.name().eq_ignore_ascii_case(s))
+        .ok_or_else(|| format!("Unknown action: {}", s))
+    }
+}
+
+/// A key with modifiers, e.g. Ctrl-N.
+///
+/// Shift is ignored for characters and BackTab, the character itself tells
+/// if shift was pressed.  Keypad bindings only match keys on the keypad,
+/// other bindings match keys anywhere on the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+    pub keypad: bool,
+}
+
+impl KeyBinding {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self {
+            code,
+            modifiers,
+            keypad: false,
+        }
+    }
+
+    /// Key on the keypad, only reported by terminals supporting keyboard
+    /// enhancement
+    pub fn keypad(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+            keypad: true,
+        }
+    }
+
+    fn matches(&self, key: &KeyEvent) -> bool {
+        // Modifier keys report themselves as modifiers as well
+        if let KeyCode::Modifier(_) = self.code {
+            return key.code == self.code;
+        }
+
+        let modifiers = match key.code {
+            KeyCode::Char(_) | KeyCode::BackTab => key.modifiers.difference(KeyModifiers::SHIFT),
+            _ => key.modifiers,
+        };
+
+        key.code == self.code
+            && modifiers == self.modifiers
+            && (!self.keypad || key.state.contains(KeyEventState::KEYPAD))
+    }
+
+    /// Only reported by terminals with keyboard enhancement enabled
+    fn needs_enhancement(&self) -> bool {
+        self.keypad || matches!(self.code, KeyCode::Modifier(_))
+    }
+}
+
+impl From<KeyCode> for KeyBinding {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+}
+
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Tab", KeyCode::Tab),
+    ("BackTab", KeyCode::BackTab),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Space", KeyCode::Char(' ')),
+    (
+        "RightCtrl",
+        KeyCode::Modifier(ModifierKeyCode::RightControl),
+    ),
+];
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    /// Parse a key name such as "Enter", "Ctrl-Enter", "F4", "Alt-b" or
+    /// "KeypadEnter"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut name = s;
+
+        // A trailing '-' is the minus key, not a separator
+        while let Some((modifier, rest)) = name.split_once('-').filter(|(_, r)| !r.is_empty()) {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("Unknown modifier in {}", s)),
+            };
+            name = rest;
+        }
+
+        let (keypad, name) = match name.get(..6) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("keypad") && name.len() > 6 => {
+                (true, &name[6..])
+            }
+            _ => (false, name),
+        };
+
+        let code = if let Some((_, code)) = KEY_NAMES
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        {
+            *code
+        } else if let Some(n) = name
+            .strip_prefix(['F', 'f'])
+            .and_then(|n| n.parse::<u8>().ok())
+        {
+            KeyCode::F(n)
+        } else {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                // Terminals report Ctrl and Alt letters in lower case
+                (Some(c), None) if !modifiers.is_empty() => KeyCode::Char(c.to_ascii_lowercase()),
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return Err(format!("Unknown key: {}", s)),
+            }
+        };
+
+        Ok(Self {
+            code,
+            modifiers,
+            keypad,
+        })
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl-"),
+            (KeyModifiers::ALT, "Alt-"),
+            (KeyModifiers::SHIFT, "Shift-"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}", name)?;
+            }
+        }
+        if self.keypad {
+            write!(f, "Keypad")?;
+        }
+
+        match KEY_NAMES.iter().find(|(_, code)| *code == self.code) {
+            Some((name, _)) => write!(f, "{}", name),
+            None => match self.code {
+                KeyCode::F(n) => write!(f, "F{}", n),
+                KeyCode::Char(c) => write!(f, "{}", c.to_ascii_uppercase()),
+                code => write!(f, "{:?}", code),
+            },
+        }
+    }
+}
+
+/// Key bindings of a form, mapping key events to actions.
+///
+/// Editing keys within fields (Backspace, Delete, Ctrl-End, ...) are not
+/// part of the key map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyMap {
+    bindings: Vec<(KeyBinding, Action)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let mut keymap = Self {
+            bindings: Vec::new(),
+        };
+
+        for (key, action) in [
+            (KeyBinding::from(KeyCode::Enter), Action::Submit),
+            (KeyCode::Esc.into(), Action::Abort),
+            (
+                KeyBinding::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+                Action::Quit,
+            ),
+            (KeyCode::F(1).into(), Action::Help),
+            (KeyCode::F(4).into(), Action::Select),
+            (KeyCode::Tab.into(), Action::NextField),
+            (KeyCode::BackTab.into(), Action::PrevField),
+            (KeyCode::Home.into(), Action::FirstField),
+            (
+                KeyBinding::new(KeyCode::Enter, KeyModifiers::SHIFT),
+                Action::NewLine,
+            ),
+            // For terminals not reporting Shift-Enter
+            (
+                KeyBinding::new(KeyCode::Char('n'), KeyModifiers::CONTROL),
+                Action::NewLine,
+            ),
+            (
+                KeyBinding::new(KeyCode::End, KeyModifiers::ALT),
+                Action::EraseInput,
+            ),
+            (
+                KeyBinding::new(KeyCode::Char('z'), KeyModifiers::CONTROL),
+                Action::Undo,
+            ),
+            (
+                KeyBinding::new(KeyCode::Char('y'), KeyModifiers::CONTROL),
+                Action::Redo,
+            ),
+            (
+                KeyBinding::new(KeyCode::Char('r'), KeyModifiers::CONTROL),
+                Action::ResetField,
+            ),
+            (
+                KeyBinding::new(KeyCode::Char('r'), KeyModifiers::ALT),
+                Action::ResetForm,
+            ),
+            // PF7 and PF8 on a 3270
+            (KeyCode::F(7).into(), Action::PrevPage),
+            (KeyCode::F(8).into(), Action::NextPage),
+            (KeyCode::PageUp.into(), Action::PrevPage),
+            (KeyCode::PageDown.into(), Action::NextPage),
+            (
+                KeyBinding::new(KeyCode::Insert, KeyModifiers::ALT),
+                Action::InsertRow,
+            ),
+            (
+                KeyBinding::new(KeyCode::Delete, KeyModifiers::ALT),
+                Action::DeleteRow,
+            ),
+            (KeyCode::Left.into(), Action::MoveLeft),
+            (KeyCode::Right.into(), Action::MoveRight),
+            (KeyCode::Up.into(), Action::MoveUp),
+            (KeyCode::Down.into(), Action::MoveDown),
+            (
+                KeyBinding::new(KeyCode::Char('d'), KeyModifiers::CONTROL),
+                Action::ToggleDebug,
+            ),
+        ] {
+            keymap.bind(key, action);
+        }
+
+        keymap
+    }
+}
+
+impl KeyMap {
+    /// Preset by name, one of "default", "3270" or "newline"
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "default" => Some(Self::default()),
+            "3270" => Some(Self::ibm_3270()),
+            "newline" => Some(Self::newline()),
+            _ => None,
+        }
+    }
+
+    /// Like a 3270 keyboard, Right-Ctrl and Keypad-Enter submit as well as
+    /// Enter
+    pub fn ibm_3270() -> Self {
+        Self::default()
+            .with_binding(
+                KeyCode::Modifier(ModifierKeyCode::RightControl),
+                Action::Submit,
+            )
+            .with_binding(KeyBinding::keypad(KeyCode::Enter), Action::Submit)
+    }
+
+    /// Enter moves to the next field, Ctrl-Enter or Keypad-Enter submit.
+    /// Ctrl-J is bound as well since most terminals send it for Ctrl-Enter,
+    /// new lines in a text area are then entered with Alt-Enter.
+    pub fn newline() -> Self {
+        Self::default()
+            .with_binding(KeyCode::Enter, Action::NextField)
+            .with_binding(
+                KeyBinding::new(KeyCode::Enter, KeyModifiers::CONTROL),
+                Action::Submit,
+            )
+            .with_binding(
+                KeyBinding::new(KeyCode::Char('j'), KeyModifiers::CONTROL),
+                Action::Submit,
+            )
+            .with_binding(KeyBinding::keypad(KeyCode::Enter), Action::Submit)
+    }
+
+    /// Bind a key to an action, replacing any earlier binding of the key
+    pub fn bind(&mut self, key: impl Into<KeyBinding>, action: Action) {
+        let key = key.into();
+
+        self.unbind(key);
+        self.bindings.push((key, action));
+    }
+
+    pub fn with_binding(mut self, key: impl Into<KeyBinding>, action: Action) -> Self {
+        self.bind(key, action);
+
+        self
+    }
+
+    pub fn unbind(&mut self, key: impl Into<KeyBinding>) {
+        let key = key.into();
+
+        self.bindings.retain(|(k, _)| *k != key);
+    }
+
+    /// Action bound to a key event, keypad bindings take precedence
+    pub fn action(&self, event: &Event) -> Option<Action> {
+        let Event::Key(key) = event else {
+            return None;
+        };
+
+        self.bindings
+            .iter()
+            .filter(|(k, _)| k.matches(key))
+            .max_by_key(|(k, _)| k.keypad)
+            .map(|(_, action)| *action)
+    }
+
+    /// First key bound to an action
+    pub fn key(&self, action: Action) -> Option<KeyBinding> {
+        self.bindings
+            .iter()
+            .find(|(_, a)| *a == action)
+            .map(|(k, _)| *k)
+    }
+
+    /// Parse a key map, one `key = action` binding per line.
+    ///
+    /// A `preset = name` line starts over from a preset, and `key = none`
+    /// removes a binding.  Lines starting with '#' are comments.
+    pub fn parse(config: &str) -> Result<Self, String> {
+        let mut keymap = Self::default();
+
+        for (number, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, action)) = line.rsplit_once('=') else {
+                return Err(format!("line {}: expected key = action", number + 1));
+            };
+            let (key, action) = (key.trim(), action.trim());
+
+            if key.eq_ignore_ascii_case("preset") {
+                keymap = Self::preset(action)
+                    .ok_or_else(|| format!("line {}: Unknown preset: {}", number + 1, action))?;
+                continue;
+            }
+
+            let key: KeyBinding = key
+                .parse()
+                .map_err(|e| format!("line {}: {}", number + 1, e))?;
+
+            if action.eq_ignore_ascii_case("none") {
+                keymap.unbind(key);
+            } else {
+                let action = action
+                    .parse()
+                    .map_err(|e| format!("line {}: {}", number + 1, e))?;
+                keymap.bind(key, action);
+            }
+        }
+
+        Ok(keymap)
+    }
+
+    /// Load a key map file, see [`KeyMap::parse`]
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let config = std::fs::read_to_string(path)?;
+
+        Self::parse(&config).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Are any keys bound that terminals only report with keyboard
+    /// enhancement enabled
+    pub(crate) fn needs_enhancement(&self) -> bool {
+        self.bindings.iter().any(|(k, _)| k.needs_enhancement())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn parse_keys() {
+        assert_eq!(
+            "Ctrl-Enter".parse(),
+            Ok(KeyBinding::new(KeyCode::Enter, KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            "ctrl-alt-X".parse(),
+            Ok(KeyBinding::new(
+                KeyCode::Char('x'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            ))
+        );
+        assert_eq!("F12".parse(), Ok(KeyBinding::from(KeyCode::F(12))));
+        assert_eq!("-".parse(), Ok(KeyBinding::from(KeyCode::Char('-'))));
+        assert_eq!(
+            "KeypadEnter".parse(),
+            Ok(KeyBinding::keypad(KeyCode::Enter))
+        );
+        assert!("Hyper-A".parse::<KeyBinding>().is_err());
+        assert!("Enterprise".parse::<KeyBinding>().is_err());
+
+        for name in ["Ctrl-N", "Shift-Enter", "F4", "KeypadEnter", "RightCtrl"] {
+            assert_eq!(name.parse::<KeyBinding>().unwrap().to_string(), name);
+        }
+    }
+
+    #[test]
+    fn presets() {
+        let enter = key(KeyCode::Enter, KeyModifiers::NONE);
+        let keypad_enter = Event::Key(KeyEvent::new_with_kind_and_state(
+            KeyCode::Enter,
+            KeyModifiers::NONE,
+            crossterm::event::KeyEventKind::Press,
+            KeyEventState::KEYPAD,
+        ));
+
+        let default = KeyMap::default();
+        assert_eq!(default.action(&enter), Some(Action::Submit));
+        assert_eq!(
+            default.action(&key(KeyCode::BackTab, KeyModifiers::SHIFT)),
+            Some(Action::PrevField)
+        );
+        assert!(!default.needs_enhancement());
+
+        let ibm = KeyMap::preset("3270").unwrap();
+        assert_eq!(
+            ibm.action(&key(
+                KeyCode::Modifier(ModifierKeyCode::RightControl),
+                KeyModifiers::CONTROL
+            )),
+            Some(Action::Submit)
+        );
+        assert!(ibm.needs_enhancement());
+
+        let newline = KeyMap::preset("newline").unwrap();
+        assert_eq!(newline.action(&enter), Some(Action::NextField));
+        assert_eq!(newline.action(&keypad_enter), Some(Action::Submit));
+        assert_eq!(
+            newline.action(&key(KeyCode::Char('j'), KeyModifiers::CONTROL)),
+            Some(Action::Submit)
+        );
+        assert_eq!(
+            newline.key(Action::Submit).unwrap().to_string(),
+            "Ctrl-Enter"
+        );
+    }
+
+    #[test]
+    fn parse_config() {
+        let keymap =
+            KeyMap::parse("# PF keys\npreset = newline\nF3 = abort\nF4 = none\nCtrl-S = Submit\n")
+                .unwrap();
+
+        assert_eq!(
+            keymap.action(&key(KeyCode::F(3), KeyModifiers::NONE)),
+            Some(Action::Abort)
+        );
+        assert_eq!(keymap.action(&key(KeyCode::F(4), KeyModifiers::NONE)), None);
+        assert_eq!(
+            keymap.action(&key(KeyCode::Char('s'), KeyModifiers::CONTROL)),
+            Some(Action::Submit)
+        );
+        assert_eq!(
+            keymap.action(&key(KeyCode::Enter, KeyModifiers::NONE)),
+            Some(Action::NextField)
+        );
+
+        assert!(KeyMap::parse("F3 = explode").is_err());
+        assert!(KeyMap::parse("preset = 3279").is_err());
+        assert!(KeyMap::parse("F3").is_err());
+    }
+}
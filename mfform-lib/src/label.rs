@@ -1,10 +1,17 @@
-use crate::pos::Pos;
+use crate::{
+    attribute::{Color, Highlight},
+    pos::Pos,
+};
 
 /// Text label
 #[derive(Debug, Clone, Eq)]
 pub struct Label {
     pub pos: Pos,
     pub text: String,
+    pub color: Option<Color>,
+    pub highlight: Option<Highlight>,
+    /// Page of a multi-page form, set when the label is added to the form
+    pub(crate) page: usize,
 }
 
 impl Ord for Label {
@@ -32,6 +39,50 @@ impl Label {
         Self {
             pos: pos.into(),
             text,
+            color: None,
+            highlight: None,
+            page: 0,
+        }
+    }
+
+    /// Create a LabelBuilder for creating a new label with attributes.
+    pub fn builder(pos: impl Into<Pos>, text: impl Into<String>) -> LabelBuilder {
+        LabelBuilder {
+            pos: pos.into(),
+            text: text.into(),
+            color: None,
+            highlight: None,
+        }
+    }
+}
+
+pub struct LabelBuilder {
+    pub pos: Pos,
+    pub text: String,
+    pub color: Option<Color>,
+    pub highlight: Option<Highlight>,
+}
+
+impl LabelBuilder {
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+
+        self
+    }
+
+    pub fn with_highlight(mut self, highlight: Highlight) -> Self {
+        self.highlight = Some(highlight);
+
+        self
+    }
+
+    pub fn build(self) -> Label {
+        Label {
+            pos: self.pos,
+            text: self.text,
+            color: self.color,
+            highlight: self.highlight,
+            page: 0,
         }
     }
 }
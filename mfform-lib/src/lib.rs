@@ -1,15 +1,55 @@
 mod app;
+mod attribute;
 mod dialog_appender;
 mod form;
+mod format;
+mod group;
+mod help_form;
+mod history;
 mod input;
+mod keymap;
 mod label;
+mod mask;
 mod pos;
 mod select_form;
+mod shape;
+mod table;
+mod text_area;
+mod theme;
+mod transform;
 mod vec_appender;
 
 pub use app::App;
 pub use app::EventResult;
+pub use attribute::Color;
+pub use attribute::Highlight;
 pub use form::Form;
+pub use format::DateFormat;
+pub use format::Format;
+pub use format::NumberFormat;
+pub use format::TimeFormat;
+pub use group::Group;
+pub use group::GroupBuilder;
 pub use input::Input;
+pub use input::InputBuilder;
+pub use input::InputKind;
+pub use input::Select;
+pub use keymap::Action;
+pub use keymap::KeyBinding;
+pub use keymap::KeyMap;
 pub use label::Label;
+pub use label::LabelBuilder;
+pub use mask::Mask;
+pub use mask::MaskChar;
 pub use pos::Pos;
+pub use shape::Shape;
+pub use shape::ShapeBuilder;
+pub use shape::ShapeKind;
+pub use table::Column;
+pub use table::Table;
+pub use table::TableBuilder;
+pub use table::TableRow;
+pub use table::TableSource;
+pub use theme::Theme;
+pub use theme::THEME_ENV;
+pub use transform::Transform;
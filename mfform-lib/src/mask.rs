@@ -0,0 +1,223 @@
+/// One position of an edit mask
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaskChar {
+    /// Fixed character, displayed as is and skipped by the cursor
+    Literal(char),
+    /// Accepts '0'..='9', PIC 9
+    Digit,
+    /// Accepts letters and space, PIC A
+    Alpha,
+    /// Accepts any character, PIC X
+    Any,
+    /// Accepts '+' or '-', PIC S
+    Sign,
+}
+
+impl MaskChar {
+    pub fn accepts(&self, c: char) -> bool {
+        match self {
+            MaskChar::Literal(_) => false,
+            MaskChar::Digit => c.is_ascii_digit(),
+            MaskChar::Alpha => c.is_alphabetic() || c == ' ',
+            MaskChar::Any => true,
+            MaskChar::Sign => c == '+' || c == '-',
+        }
+    }
+
+    pub fn is_literal(&self) -> bool {
+        matches!(self, MaskChar::Literal(_))
+    }
+}
+
+/// Longest repeat count in a picture clause, well beyond any screen width
+const MAX_REPEAT: usize = 256;
+
+/// Edit mask, fixing the structure of a field value.
+///
+/// Values of masked fields always contain the literals, positions not yet
+/// filled in are blank.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask(pub Vec<MaskChar>);
+
+impl Mask {
+    /// Mask from a template, '9' is a digit and anything else a literal,
+    /// e.g. "9999-99-99".
+    pub fn from_template(template: &str) -> Self {
+        Self(
+            template
+                .chars()
+                .map(|c| match c {
+                    '9' => MaskChar::Digit,
+                    c => MaskChar::Literal(c),
+                })
+                .collect(),
+        )
+    }
+
+    /// Mask from a COBOL style picture clause, e.g. "999-99-9999", "A(10)" or
+    /// "S9(5)V99".
+    ///
+    /// 9, A, X and S are input positions and B is shown as a space.  V is
+    /// the implied decimal point of COBOL, it takes no position and is not
+    /// shown.  A count in parenthesis, up to 256, repeats the previous
+    /// position, anything else is a literal.
+    pub fn from_picture(picture: &str) -> Result<Self, String> {
+        let mut mask: Vec<MaskChar> = Vec::new();
+        let mut chars = picture.chars();
+
+        while let Some(c) = chars.next() {
+            let m = match c.to_ascii_uppercase() {
+                '9' => MaskChar::Digit,
+                'A' => MaskChar::Alpha,
+                'X' => MaskChar::Any,
+                'S' => MaskChar::Sign,
+                'V' => continue,
+                'B' => MaskChar::Literal(' '),
+                '(' => {
+                    let count: String = chars.by_ref().take_while(|c| *c != ')').collect();
+                    let count = count
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid repeat count ({}) in {}", count, picture))?;
+                    if count > MAX_REPEAT {
+                        return Err(format!("Repeat count ({}) too large in {}", count, picture));
+                    }
+                    let Some(last) = mask.last().copied() else {
+                        return Err(format!("Nothing to repeat in {}", picture));
+                    };
+
+                    mask.extend(std::iter::repeat_n(last, count.saturating_sub(1)));
+                    continue;
+                }
+                _ => MaskChar::Literal(c),
+            };
+
+            mask.push(m);
+        }
+
+        Ok(Self(mask))
+    }
+
+    /// Only the input positions of a value, without literals
+    pub fn raw(&self, value: &str) -> String {
+        value
+            .chars()
+            .zip(self.0.iter())
+            .filter(|(_, m)| !m.is_literal())
+            .map(|(c, _)| c)
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Value with only the literals filled in
+    pub fn blank(&self) -> String {
+        self.0
+            .iter()
+            .map(|m| match m {
+                MaskChar::Literal(c) => *c,
+                _ => ' ',
+            })
+            .collect()
+    }
+
+    /// Has nothing been entered into the value
+    pub fn is_blank(&self, value: &str) -> bool {
+        value
+            .chars()
+            .zip(self.0.iter())
+            .all(|(c, m)| m.is_literal() || c == ' ')
+    }
+
+    /// Is every input position filled with a character it accepts, A and X
+    /// positions accept spaces
+    pub fn is_complete(&self, value: &str) -> bool {
+        let chars: Vec<char> = value.chars().collect();
+
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| !m.is_literal())
+            .all(|(i, m)| chars.get(i).is_some_and(|c| m.accepts(*c)))
+    }
+
+    /// First input position at or after `index`
+    pub(crate) fn next_input(&self, index: usize) -> Option<usize> {
+        (index..self.len()).find(|i| !self.0[*i].is_literal())
+    }
+
+    /// Last input position before `index`
+    pub(crate) fn prev_input(&self, index: usize) -> Option<usize> {
+        (0..index.min(self.len()))
+            .rev()
+            .find(|i| !self.0[*i].is_literal())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template() {
+        let mask = Mask::from_template("99.99");
+
+        assert_eq!(mask.len(), 5);
+        assert_eq!(mask.blank(), "  .  ");
+        assert!(mask.is_blank("  .  "));
+        assert!(!mask.is_blank(" 1.  "));
+    }
+
+    #[test]
+    fn picture() {
+        assert_eq!(
+            Mask::from_picture("999-99-9999").unwrap(),
+            Mask::from_template("999-99-9999")
+        );
+        assert_eq!(
+            Mask::from_picture("A(3)X").unwrap().0,
+            vec![
+                MaskChar::Alpha,
+                MaskChar::Alpha,
+                MaskChar::Alpha,
+                MaskChar::Any
+            ]
+        );
+
+        // The implied decimal point takes no position
+        let mask = Mask::from_picture("S9(5)V99").unwrap();
+        assert_eq!(mask.len(), 8);
+        assert_eq!(mask.blank(), " ".repeat(8));
+        assert_eq!(mask.raw("-0001250"), "-0001250");
+
+        assert!(Mask::from_picture("9(x)").is_err());
+        assert!(Mask::from_picture("(3)").is_err());
+        assert!(Mask::from_picture("A(256)").is_ok());
+        assert!(Mask::from_picture("A(65535)").is_err());
+    }
+
+    #[test]
+    fn complete() {
+        let mask = Mask::from_picture("999-99-9999").unwrap();
+
+        assert!(mask.is_complete("123-45-6789"));
+        assert!(!mask.is_complete("123-4 -    "));
+        assert!(!mask.is_complete("123-45"));
+        assert!(Mask::from_picture("A(3)X").unwrap().is_complete("ab  "));
+    }
+
+    #[test]
+    fn navigation() {
+        let mask = Mask::from_template("99-99");
+
+        assert_eq!(mask.next_input(2), Some(3));
+        assert_eq!(mask.next_input(5), None);
+        assert_eq!(mask.prev_input(3), Some(1));
+        assert_eq!(mask.prev_input(0), None);
+    }
+}
@@ -8,6 +8,7 @@ pub struct Pos {
 }
 
 impl Pos {
+    /// Keep a form position within the form size
     pub(crate) fn constrain<I: Into<Self> + Copy>(self, other: I) -> Self {
         Self {
             x: max(min(self.x, other.into().x - 1), 0),
@@ -15,6 +16,24 @@ impl Pos {
         }
     }
 
+    /// Screen position of a form position, with the form scrolled by
+    /// `offset`
+    pub(crate) fn to_screen(self, offset: Self) -> Self {
+        Self {
+            x: self.x.saturating_sub(offset.x),
+            y: self.y.saturating_sub(offset.y),
+        }
+    }
+
+    /// Form position of a screen position, with the form scrolled by
+    /// `offset`
+    pub(crate) fn to_form(self, offset: Self) -> Self {
+        Self {
+            x: self.x + offset.x,
+            y: self.y + offset.y,
+        }
+    }
+
     pub(crate) fn within(self, other: Self, length: u16) -> Option<usize> {
         if self.x >= other.x && self.x <= other.x + length && self.y == other.y {
             Some((self.x - other.x) as usize)
@@ -23,12 +42,23 @@ impl Pos {
         }
     }
 
-    pub(crate) fn move_x(self, by: i16, max: u16) -> Self {
-        Pos {
-            x: (self.x as i16 + by) as u16,
-            y: self.y,
+    /// Like `within`, but for an area spanning `height` rows.  Returns the
+    /// column and row offset into the area.
+    pub(crate) fn within_area(
+        self,
+        other: Self,
+        width: u16,
+        height: u16,
+    ) -> Option<(usize, usize)> {
+        if self.x >= other.x
+            && self.x <= other.x + width
+            && self.y >= other.y
+            && self.y < other.y + height
+        {
+            Some(((self.x - other.x) as usize, (self.y - other.y) as usize))
+        } else {
+            None
         }
-        .constrain((max, u16::MAX))
     }
 }
 
@@ -99,6 +129,15 @@ mod tests {
         assert_eq!(res, None);
     }
 
+    #[test]
+    fn within_area() {
+        let field: Pos = (2, 2).into();
+
+        assert_eq!(Pos::from((4, 3)).within_area(field, 4, 3), Some((2, 1)));
+        assert_eq!(Pos::from((4, 5)).within_area(field, 4, 3), None);
+        assert_eq!(Pos::from((4, 1)).within_area(field, 4, 3), None);
+    }
+
     #[test]
     fn ordering() {
         let test_pos: Pos = (2, 2).into();
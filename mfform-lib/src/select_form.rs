@@ -1,45 +1,23 @@
-use crossterm::{
-    cursor,
-    event::{Event, KeyCode},
-    style,
-    terminal::{self, ClearType},
-    QueueableCommand,
-};
-use log::debug;
-use std::io::{self, Stdout, Write};
+use crossterm::event::{Event, MouseButton, MouseEvent, MouseEventKind};
+use std::io::{self, Stdout};
 
 use crate::{
     app::{EventHandlerResult, EventResult},
     input::Select,
+    keymap::{Action, KeyMap},
     pos::Pos,
+    table::{Table, TableRow},
+    theme::Theme,
 };
 
-#[derive(Debug, Clone)]
-pub(crate) struct Item {
-    choice: char,
-    id: String,
-    text: String,
-}
-
-impl From<&(String, String)> for Item {
-    fn from(value: &(String, String)) -> Self {
-        Self {
-            choice: ' ',
-            id: value.0.to_string(),
-            text: value.1.to_string(),
-        }
-    }
-}
-
 static FIRST_FIELD_POS: Pos = Pos { x: 20, y: 5 };
 
+/// Popup for picking from the SELECT items of a field, a one column table
+/// where the S line command selects an item
 #[derive(Debug, Clone)]
 pub struct SelectForm {
-    pub(crate) items: Vec<Item>,
-    pub(crate) current_pos: Pos,
-    pub(crate) size: Pos,
+    pub(crate) table: Table,
     pub(crate) select_type: Select,
-    pub(crate) error_message: Option<&'static str>,
 }
 
 impl SelectForm {
@@ -48,259 +26,116 @@ impl SelectForm {
         size: impl Into<Pos>,
         select_type: Select,
     ) -> io::Result<Self> {
-        Ok(Self {
-            items: items.iter().map(Item::from).collect(),
-            current_pos: FIRST_FIELD_POS,
-            size: size.into(),
-            select_type,
-            error_message: None,
-        })
+        let size = size.into();
+        let width = items
+            .iter()
+            .map(|(_, text)| text.chars().count() as u16)
+            .max()
+            .unwrap_or(0)
+            .min(size.x.saturating_sub(FIRST_FIELD_POS.x + 3));
+
+        let table = Table::builder(size)
+            .with_column("", width)
+            .with_rows(items.iter().map(|(id, text)| TableRow::new(id, [text])))
+            .with_commands("S")
+            .with_layout(FIRST_FIELD_POS, 2)
+            .build();
+
+        Ok(Self { table, select_type })
     }
 
-    fn display_choice(stdout: &mut Stdout, pos: impl Into<Pos>, item: &Item) -> io::Result<()> {
-        let pos = pos.into();
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.table.theme = theme;
+
+        self
+    }
 
-        stdout
-            .queue(cursor::MoveTo(pos.x, pos.y))?
-            .queue(style::SetAttribute(style::Attribute::Underlined))?
-            .queue(style::Print(item.choice))?
-            .queue(style::SetAttribute(style::Attribute::NoUnderline))?
-            .queue(style::Print(' '))?
-            .queue(style::Print(&item.text))?;
+    /// Draw the border with plain ASCII
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.table.ascii = ascii;
 
-        Ok(())
+        self
     }
 
-    pub fn display(&mut self, stdout: &mut Stdout) -> io::Result<()> {
-        // Clear dialog
-        stdout
-            .queue(cursor::MoveTo(self.size.x, self.size.y))?
-            .queue(terminal::Clear(ClearType::FromCursorUp))?;
-
-        if let Some(error_message) = self.error_message {
-            stdout
-                .queue(cursor::MoveTo(
-                    (self.size.x / 2).saturating_sub(error_message.len() as u16 / 2),
-                    self.size.y - 1,
-                ))?
-                .queue(style::SetForegroundColor(style::Color::DarkRed))?
-                .queue(style::Print(error_message))?;
-        }
-        // Border
-        stdout.queue(style::SetForegroundColor(style::Color::DarkGreen))?;
-        for y in 0..24 {
-            stdout
-                .queue(cursor::MoveTo(80, y))?
-                .queue(style::Print('│'))?;
-        }
-        stdout
-            .queue(cursor::MoveTo(0, 24))?
-            .queue(style::Print("─".repeat(80)))?
-            .queue(style::Print('┘'))?;
-
-        stdout
-            .queue(cursor::MoveTo(2, 24))?
-            .queue(style::Print(" Esc=Abort "))?
-            .queue(style::Print('─'))?
-            .queue(style::Print(" Enter=Submit "))?;
-
-        for (i, item) in self.items.clone().into_iter().enumerate() {
-            Self::display_choice(stdout, (20, 5 + (i as u16 * 2)), &item)?;
-        }
+    /// Use the key bindings of the form
+    pub fn with_keymap(mut self, keymap: KeyMap) -> Self {
+        self.table.keymap = keymap;
 
-        stdout.queue(cursor::MoveTo(self.current_pos.x, self.current_pos.y))?;
-        stdout.queue(cursor::SetCursorStyle::SteadyUnderScore)?;
+        self
+    }
 
-        stdout.flush()
+    pub fn display(&mut self, stdout: &mut Stdout) -> io::Result<()> {
+        self.table.display(stdout)
     }
 
     pub(crate) fn get_selection(&self) -> Vec<String> {
-        self.items
-            .iter()
-            .filter(|i| i.choice == 's')
-            .map(|i| i.id.to_string())
+        self.table
+            .get_commands()
+            .into_iter()
+            .filter(|(_, command)| *command == 'S')
+            .map(|(id, _)| id)
             .collect()
     }
 }
 
 impl SelectForm {
     pub fn event_handler(&mut self, event: &Event) -> io::Result<EventHandlerResult> {
-        match event {
-            Event::Key(k) if k.code == KeyCode::Esc => {
-                return Ok(EventHandlerResult::Handled(EventResult::Abort));
-            }
-            Event::Key(k) if k.code == KeyCode::Enter => {
-                if self.select_type == Select::Single && self.get_selection().len() <= 1 {
-                    return Ok(EventHandlerResult::Handled(EventResult::Submit));
-                } else {
-                    self.error_message = Some("Too many selected");
-                }
-            }
-            Event::Key(k) if k.code == KeyCode::Left => {
-                self.move_event(k.code);
-            }
-            Event::Key(k) if k.code == KeyCode::Right => {
-                self.move_event(k.code);
-            }
-            Event::Key(k) if k.code == KeyCode::Up => {
-                self.move_event(k.code);
-            }
-            Event::Key(k) if k.code == KeyCode::Down => {
-                self.move_event(k.code);
-            }
-            Event::Key(k) if k.code == KeyCode::Tab => {
-                self.next_input();
-            }
-            Event::Key(k) if k.code == KeyCode::BackTab => {
-                self.prev_input();
-            }
-            Event::Key(k) if k.code == KeyCode::Backspace => {
-                self.key_backspace()?;
-            }
-            Event::Key(k) if k.code == KeyCode::Delete => {
-                self.key_delete()?;
-            }
-            Event::Key(k) if k.modifiers.is_empty() => {
-                if let KeyCode::Char(c) = k.code {
-                    self.key(c);
-                } else {
-                    return Ok(EventHandlerResult::NotHandled);
-                }
-            }
-            _ => return Ok(EventHandlerResult::NotHandled),
-        }
-
-        Ok(EventHandlerResult::Handled(EventResult::None))
-    }
-
-    pub fn move_event(&mut self, code: KeyCode) {
-        self.current_pos = match code {
-            KeyCode::Left => Pos {
-                x: self.current_pos.x.checked_sub(1).unwrap_or_default(),
-                y: self.current_pos.y,
-            },
-            KeyCode::Right => Pos {
-                x: self.current_pos.x + 1,
-                y: self.current_pos.y,
-            },
-            KeyCode::Up => Pos {
-                x: self.current_pos.x,
-                y: self.current_pos.y.checked_sub(1).unwrap_or_default(),
-            },
-            KeyCode::Down => Pos {
-                x: self.current_pos.x,
-                y: self.current_pos.y + 1,
-            },
-            _ => self.current_pos,
+        if let Event::Mouse(mouse) = event {
+            return self.mouse_event(mouse);
         }
-        .constrain(self.size)
-    }
-
-    pub fn key(&mut self, key: char) {
-        if self.current_pos.x != 20 {
-            debug!("Not correct column: {:?}", self.current_pos);
-            return;
+        if self.table.keymap.action(event) == Some(Action::Submit) {
+            return Ok(self.submit());
         }
 
-        let i = self.current_pos.y - 5;
-
-        if i % 2 != 0 {
-            debug!("Not in event row: {:?}", self.current_pos);
-            return;
-        }
-
-        let choice = i / 2;
-
-        if choice > self.items.len() as u16 {
-            debug!("Below lowest item: {:?}", self.current_pos);
-            return;
-        }
-
-        if let Some(c) = self.items.get_mut(choice as usize) {
-            c.choice = key;
-        }
+        self.table.event_handler(event)
     }
 
-    pub fn key_backspace(&mut self) -> io::Result<()> {
-        self.key(' ');
-        Ok(())
-    }
-
-    pub fn key_delete(&mut self) -> io::Result<()> {
-        self.key(' ');
-        Ok(())
-    }
-
-    pub(crate) fn find_next_input(&mut self) -> Option<Pos> {
-        fn pos_to_flat(size: Pos, pos: Pos) -> u16 {
-            (size.x * pos.y) + pos.x
-        }
-
-        let first_field = pos_to_flat(self.size, FIRST_FIELD_POS);
-        let current_pos = pos_to_flat(self.size, self.current_pos);
-
-        if current_pos < first_field {
-            return Some(FIRST_FIELD_POS);
+    fn submit(&mut self) -> EventHandlerResult {
+        if self.select_type == Select::Single && self.get_selection().len() <= 1 {
+            return EventHandlerResult::Handled(EventResult::Submit);
         }
+        self.table.error_message = Some("Too many selected".to_string());
 
-        let difference = current_pos - first_field;
-
-        let diff_and_some = difference + (self.size.x * 2);
-
-        let choice = diff_and_some / self.size.x / 2;
-
-        if choice >= self.items.len() as u16 {
-            Some(FIRST_FIELD_POS)
-        } else {
-            Some(Pos {
-                x: FIRST_FIELD_POS.x,
-                y: FIRST_FIELD_POS.y + (2 * (choice)),
-            })
-        }
+        EventHandlerResult::Handled(EventResult::None)
     }
 
-    pub(crate) fn find_prev_input(&mut self) -> Option<Pos> {
-        fn pos_to_flat(size: Pos, pos: Pos) -> u16 {
-            (size.x * pos.y) + pos.x
-        }
-
-        let first_field = pos_to_flat(self.size, FIRST_FIELD_POS);
-        let current_pos = pos_to_flat(self.size, self.current_pos);
-
-        if current_pos < first_field {
-            return Some(Pos {
-                x: FIRST_FIELD_POS.x,
-                y: FIRST_FIELD_POS.y + (self.items.len() as u16 * 2) - 2,
-            });
-        }
-
-        let difference = current_pos.wrapping_sub(first_field).wrapping_sub(1);
-
-        let choice = difference / self.size.x / 2;
+    /// Clicking an item selects it, a single select is submitted right away.
+    /// Clicking the legend acts as the key shown and the wheel moves between
+    /// items.
+    fn mouse_event(&mut self, mouse: &MouseEvent) -> io::Result<EventHandlerResult> {
+        let pos = Pos {
+            x: mouse.column,
+            y: mouse.row,
+        };
 
-        if choice >= self.items.len() as u16 {
-            Some(Pos {
-                x: FIRST_FIELD_POS.x,
-                y: FIRST_FIELD_POS.y + (self.items.len() as u16 * 2) - 2,
-            })
-        } else {
-            Some(Pos {
-                x: FIRST_FIELD_POS.x,
-                y: FIRST_FIELD_POS.y + (2 * (choice)),
-            })
-        }
-    }
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                match self.table.legend_action(pos) {
+                    Some(Action::Submit) => return Ok(self.submit()),
+                    Some(action) => return Ok(self.table.action(action)),
+                    None => (),
+                }
+                let Some(index) = self.table.row_at(pos) else {
+                    return Ok(EventHandlerResult::Handled(EventResult::None));
+                };
+                self.table.focus_row(index);
+
+                let rows = &mut self.table.rows;
+                if self.select_type == Select::Single {
+                    rows.iter_mut().for_each(|r| r.command = ' ');
+                    rows[index].command = 'S';
+                    return Ok(EventHandlerResult::Handled(EventResult::Submit));
+                }
 
-    pub fn next_input(&mut self) {
-        if let Some(pos) = self.find_next_input() {
-            self.current_pos = pos;
+                let row = &mut rows[index];
+                row.command = if row.command == 'S' { ' ' } else { 'S' };
+            }
+            MouseEventKind::ScrollDown => self.table.next_input(),
+            MouseEventKind::ScrollUp => self.table.prev_input(),
+            _ => return Ok(EventHandlerResult::NotHandled),
         }
-    }
 
-    pub fn prev_input(&mut self) {
-        if let Some(pos) = self.find_prev_input() {
-            self.current_pos = pos;
-        }
+        Ok(EventHandlerResult::Handled(EventResult::None))
     }
 }
 
@@ -311,6 +146,55 @@ mod tests {
 
     use super::{SelectForm, FIRST_FIELD_POS};
 
+    #[test]
+    fn test_mouse_select() {
+        use crate::app::{EventHandlerResult, EventResult};
+        use crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+        let mouse = |kind, column, row| {
+            Event::Mouse(MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+        let click = |column, row| mouse(MouseEventKind::Down(MouseButton::Left), column, row);
+        let mut form = SelectForm::new(
+            &[
+                ("1".to_string(), "item1".to_string()),
+                ("2".to_string(), "item2".to_string()),
+            ],
+            (80, 24),
+            Select::Single,
+        )
+        .unwrap();
+
+        form.event_handler(&mouse(MouseEventKind::ScrollDown, 0, 0))
+            .unwrap();
+        assert_eq!(form.table.current_pos, (20, 7).into());
+        form.event_handler(&mouse(MouseEventKind::ScrollUp, 0, 0))
+            .unwrap();
+        assert_eq!(form.table.current_pos, FIRST_FIELD_POS);
+
+        // Between and past the items
+        form.event_handler(&click(22, 6)).unwrap();
+        form.event_handler(&click(30, 7)).unwrap();
+        assert!(form.get_selection().is_empty());
+
+        assert_eq!(
+            form.event_handler(&click(24, 7)).unwrap(),
+            EventHandlerResult::Handled(EventResult::Submit)
+        );
+        assert_eq!(form.get_selection(), vec!["2".to_string()]);
+        assert_eq!(form.table.current_pos, (20, 7).into());
+
+        assert_eq!(
+            form.event_handler(&click(4, 24)).unwrap(),
+            EventHandlerResult::Handled(EventResult::Abort)
+        );
+    }
+
     #[test]
     fn test_next_before_first() {
         let mut form = SelectForm::new(
@@ -324,9 +208,9 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = (0, 0).into();
+        form.table.current_pos = (0, 0).into();
 
-        assert_eq!(form.find_next_input(), Some(FIRST_FIELD_POS));
+        assert_eq!(form.table.find_next_input(), Some(FIRST_FIELD_POS));
     }
 
     #[test]
@@ -343,10 +227,10 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = FIRST_FIELD_POS;
+        form.table.current_pos = FIRST_FIELD_POS;
 
         assert_eq!(
-            form.find_next_input(),
+            form.table.find_next_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y + 2
@@ -368,13 +252,13 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = Pos {
+        form.table.current_pos = Pos {
             x: FIRST_FIELD_POS.x,
             y: FIRST_FIELD_POS.y + 2,
         };
 
         assert_eq!(
-            form.find_next_input(),
+            form.table.find_next_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y + 4
@@ -396,13 +280,13 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = Pos {
+        form.table.current_pos = Pos {
             x: FIRST_FIELD_POS.x + 3,
             y: FIRST_FIELD_POS.y + 3,
         };
 
         assert_eq!(
-            form.find_next_input(),
+            form.table.find_next_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y + 4
@@ -424,13 +308,13 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = Pos {
+        form.table.current_pos = Pos {
             x: FIRST_FIELD_POS.x,
             y: FIRST_FIELD_POS.y + 2,
         };
 
         assert_eq!(
-            form.find_next_input(),
+            form.table.find_next_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y + 4
@@ -452,13 +336,13 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = Pos {
+        form.table.current_pos = Pos {
             x: FIRST_FIELD_POS.x,
             y: FIRST_FIELD_POS.y + 6,
         };
 
         assert_eq!(
-            form.find_next_input(),
+            form.table.find_next_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y
@@ -480,13 +364,13 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = Pos {
+        form.table.current_pos = Pos {
             x: FIRST_FIELD_POS.x,
             y: FIRST_FIELD_POS.y + 9,
         };
 
         assert_eq!(
-            form.find_next_input(),
+            form.table.find_next_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y
@@ -508,10 +392,10 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = (0, 0).into();
+        form.table.current_pos = (0, 0).into();
 
         assert_eq!(
-            form.find_prev_input(),
+            form.table.find_prev_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y + 6
@@ -533,10 +417,10 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = FIRST_FIELD_POS;
+        form.table.current_pos = FIRST_FIELD_POS;
 
         assert_eq!(
-            form.find_prev_input(),
+            form.table.find_prev_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y + 6
@@ -558,13 +442,13 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = Pos {
+        form.table.current_pos = Pos {
             x: FIRST_FIELD_POS.x,
             y: FIRST_FIELD_POS.y + 2,
         };
 
         assert_eq!(
-            form.find_prev_input(),
+            form.table.find_prev_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y
@@ -586,13 +470,13 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = Pos {
+        form.table.current_pos = Pos {
             x: FIRST_FIELD_POS.x + 2,
             y: FIRST_FIELD_POS.y + 3,
         };
 
         assert_eq!(
-            form.find_prev_input(),
+            form.table.find_prev_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y + 2
@@ -614,13 +498,13 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = Pos {
+        form.table.current_pos = Pos {
             x: FIRST_FIELD_POS.x,
             y: FIRST_FIELD_POS.y + 4,
         };
 
         assert_eq!(
-            form.find_prev_input(),
+            form.table.find_prev_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y + 2
@@ -642,13 +526,13 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = Pos {
+        form.table.current_pos = Pos {
             x: FIRST_FIELD_POS.x,
             y: FIRST_FIELD_POS.y + 6,
         };
 
         assert_eq!(
-            form.find_prev_input(),
+            form.table.find_prev_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y + 4
@@ -670,13 +554,13 @@ mod tests {
         )
         .unwrap();
 
-        form.current_pos = Pos {
+        form.table.current_pos = Pos {
             x: FIRST_FIELD_POS.x,
             y: FIRST_FIELD_POS.y + 9,
         };
 
         assert_eq!(
-            form.find_prev_input(),
+            form.table.find_prev_input(),
             Some(Pos {
                 x: FIRST_FIELD_POS.x,
                 y: FIRST_FIELD_POS.y + 6
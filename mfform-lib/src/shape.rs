@@ -0,0 +1,229 @@
+use crate::{attribute::Color, pos::Pos};
+
+/// Kind of line art
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeKind {
+    /// Frame around an area, the title is shown in the top line
+    Frame {
+        width: u16,
+        height: u16,
+        title: Option<String>,
+    },
+    HLine {
+        length: u16,
+    },
+    VLine {
+        length: u16,
+    },
+}
+
+/// Lines and boxes for grouping screen sections, drawn under labels and
+/// fields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape {
+    pub pos: Pos,
+    pub kind: ShapeKind,
+    pub color: Option<Color>,
+    /// Page of a multi-page form, set when the shape is added to the form
+    pub(crate) page: usize,
+}
+
+/// Line drawing characters, horizontal, vertical and the corners from the
+/// top left clockwise
+struct LineChars {
+    horizontal: char,
+    vertical: char,
+    corners: [char; 4],
+}
+
+const BOX_DRAWING: LineChars = LineChars {
+    horizontal: '─',
+    vertical: '│',
+    corners: ['┌', '┐', '┘', '└'],
+};
+
+const ASCII: LineChars = LineChars {
+    horizontal: '-',
+    vertical: '|',
+    corners: ['+', '+', '+', '+'],
+};
+
+/// Characters of the screen border, the vertical and horizontal lines and
+/// the bottom right corner
+pub(crate) fn border_chars(ascii: bool) -> (char, char, char) {
+    let chars = if ascii { &ASCII } else { &BOX_DRAWING };
+
+    (chars.vertical, chars.horizontal, chars.corners[2])
+}
+
+impl Shape {
+    /// Create a ShapeBuilder for a frame of the given outside dimensions
+    pub fn frame(pos: impl Into<Pos>, width: u16, height: u16) -> ShapeBuilder {
+        ShapeBuilder::new(
+            pos,
+            ShapeKind::Frame {
+                width,
+                height,
+                title: None,
+            },
+        )
+    }
+
+    /// Create a ShapeBuilder for a horizontal line
+    pub fn hline(pos: impl Into<Pos>, length: u16) -> ShapeBuilder {
+        ShapeBuilder::new(pos, ShapeKind::HLine { length })
+    }
+
+    /// Create a ShapeBuilder for a vertical line
+    pub fn vline(pos: impl Into<Pos>, length: u16) -> ShapeBuilder {
+        ShapeBuilder::new(pos, ShapeKind::VLine { length })
+    }
+
+    /// Text to print and where, using box drawing characters or plain ASCII.
+    /// The inside of a frame is left alone.
+    pub(crate) fn segments(&self, ascii: bool) -> Vec<(Pos, String)> {
+        let chars = if ascii { &ASCII } else { &BOX_DRAWING };
+        let Pos { x, y } = self.pos;
+        let at = |dx: u16, dy: u16| Pos {
+            x: x + dx,
+            y: y + dy,
+        };
+
+        match &self.kind {
+            ShapeKind::HLine { length } => {
+                vec![(
+                    self.pos,
+                    chars.horizontal.to_string().repeat(*length as usize),
+                )]
+            }
+            ShapeKind::VLine { length } => (0..*length)
+                .map(|dy| (at(0, dy), chars.vertical.to_string()))
+                .collect(),
+            ShapeKind::Frame {
+                width,
+                height,
+                title,
+            } => {
+                let inside = width.saturating_sub(2) as usize;
+                let [top_left, top_right, bottom_right, bottom_left] = chars.corners;
+
+                let mut top: Vec<char> = vec![chars.horizontal; inside];
+                if let Some(title) = title.as_ref().filter(|_| inside > 3) {
+                    let title: Vec<char> =
+                        format!(" {} ", title).chars().take(inside - 2).collect();
+                    top.splice(1..1 + title.len(), title);
+                }
+
+                let mut segments = vec![(
+                    self.pos,
+                    format!("{}{}{}", top_left, String::from_iter(top), top_right),
+                )];
+                for dy in 1..height.saturating_sub(1) {
+                    segments.push((at(0, dy), chars.vertical.to_string()));
+                    segments.push((at(width.saturating_sub(1), dy), chars.vertical.to_string()));
+                }
+                if *height > 1 {
+                    segments.push((
+                        at(0, height - 1),
+                        format!(
+                            "{}{}{}",
+                            bottom_left,
+                            chars.horizontal.to_string().repeat(inside),
+                            bottom_right
+                        ),
+                    ));
+                }
+
+                segments
+            }
+        }
+    }
+}
+
+pub struct ShapeBuilder {
+    pub pos: Pos,
+    pub kind: ShapeKind,
+    pub color: Option<Color>,
+}
+
+impl ShapeBuilder {
+    fn new(pos: impl Into<Pos>, kind: ShapeKind) -> Self {
+        Self {
+            pos: pos.into(),
+            kind,
+            color: None,
+        }
+    }
+
+    /// Title shown in the top line of a frame, ignored for lines
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        if let ShapeKind::Frame { title: t, .. } = &mut self.kind {
+            *t = Some(title.into());
+        }
+
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+
+        self
+    }
+
+    pub fn build(self) -> Shape {
+        Shape {
+            pos: self.pos,
+            kind: self.kind,
+            color: self.color,
+            page: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines() {
+        assert_eq!(
+            Shape::hline((2, 3), 4).build().segments(false),
+            vec![((2, 3).into(), "────".to_string())]
+        );
+        assert_eq!(
+            Shape::vline((2, 3), 2).build().segments(true),
+            vec![
+                ((2, 3).into(), "|".to_string()),
+                ((2, 4).into(), "|".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn frame() {
+        let frame = Shape::frame((1, 1), 12, 3).with_title("Owner").build();
+
+        assert_eq!(
+            frame.segments(false),
+            vec![
+                ((1, 1).into(), "┌─ Owner ──┐".to_string()),
+                ((1, 2).into(), "│".to_string()),
+                ((12, 2).into(), "│".to_string()),
+                ((1, 3).into(), "└──────────┘".to_string()),
+            ]
+        );
+        assert_eq!(frame.segments(true)[0].1, "+- Owner --+");
+        assert_eq!(border_chars(true), ('|', '-', '+'));
+        assert_eq!(border_chars(false), ('│', '─', '┘'));
+
+        // Long titles are cut to fit
+        let frame = Shape::frame((0, 0), 8, 2).with_title("Long title").build();
+        assert_eq!(
+            frame.segments(true),
+            vec![
+                ((0, 0).into(), "+- Lon-+".to_string()),
+                ((0, 1).into(), "+------+".to_string()),
+            ]
+        );
+    }
+}
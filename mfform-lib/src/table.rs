@@ -0,0 +1,830 @@
+use crossterm::{
+    cursor,
+    event::{Event, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
+    style,
+    terminal::{self, ClearType},
+    QueueableCommand,
+};
+use log::debug;
+use std::io::{self, Stdout, Write};
+
+use crate::{
+    app::{EventHandlerResult, EventResult},
+    keymap::{Action, KeyMap},
+    pos::Pos,
+    shape,
+    theme::Theme,
+};
+
+/// Column of a table, cells are cut to the width
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    pub title: String,
+    pub width: u16,
+}
+
+/// Row of a table, reported by `id` along with the line command entered on
+/// it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableRow {
+    pub id: String,
+    pub cells: Vec<String>,
+    /// Line command, blank when none was entered
+    pub(crate) command: char,
+}
+
+impl TableRow {
+    pub fn new(id: impl Into<String>, cells: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            id: id.into(),
+            cells: cells.into_iter().map(Into::into).collect(),
+            command: ' ',
+        }
+    }
+}
+
+/// Source of the rows of a table, e.g. the result of a query
+pub trait TableSource {
+    fn rows(&self) -> Vec<TableRow>;
+}
+
+/// Line command field of the first row shown, and the header row above it
+const FIRST_ROW_POS: Pos = Pos { x: 2, y: 3 };
+
+/// Scrollable table with a one character line command in front of each row,
+/// like ISPF table displays.  Rows are scrolled with the next-page and
+/// prev-page keys, F7/F8 by default, and submitting returns the commands
+/// entered, see `get_commands`.  Keys are looked up in a [`KeyMap`] like on a
+/// form.
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub(crate) title: Option<String>,
+    pub(crate) columns: Vec<Column>,
+    pub(crate) rows: Vec<TableRow>,
+    /// Line commands accepted, any command when empty
+    pub(crate) commands: Vec<char>,
+    /// Line command field of the first row shown
+    pub(crate) origin: Pos,
+    /// Screen rows per table row
+    pub(crate) spacing: u16,
+    /// First row shown
+    pub(crate) scroll: usize,
+    pub(crate) current_pos: Pos,
+    pub(crate) size: Pos,
+    /// Part of the screen used, the size unless the terminal is smaller
+    pub(crate) viewport: Pos,
+    pub(crate) error_message: Option<String>,
+    pub(crate) theme: Theme,
+    pub(crate) keymap: KeyMap,
+    pub(crate) mouse: bool,
+    pub(crate) ascii: bool,
+}
+
+impl Table {
+    /// Create a TableBuilder for a table filling a screen of `size`.  The
+    /// line commands default to S (select), D (delete) and E (edit), the
+    /// theme is taken from the environment, see [`Theme::from_env`].
+    ///```no_run
+    ///# use mfform_lib::{App, EventResult, Table};
+    ///let mut table = Table::builder((80, 24))
+    ///    .with_title("Members")
+    ///    .with_column("Name", 8)
+    ///    .with_column("Changed", 10)
+    ///    .with_row("1", ["ALPHA", "2024-01-02"])
+    ///    .with_row("2", ["BETA", "2024-03-04"])
+    ///    .build();
+    ///
+    ///let mut app = App::with_writer(std::io::stdout());
+    ///app.init()?;
+    ///if app.execute_table(&mut table)? == EventResult::Submit {
+    ///    for (id, command) in table.get_commands() {
+    ///        println!("{} {}", id, command);
+    ///    }
+    ///}
+    ///# Ok::<(), std::io::Error>(())
+    ///```
+    pub fn builder(size: impl Into<Pos>) -> TableBuilder {
+        TableBuilder {
+            title: None,
+            columns: Vec::new(),
+            rows: Vec::new(),
+            commands: vec!['S', 'D', 'E'],
+            origin: FIRST_ROW_POS,
+            spacing: 1,
+            size: size.into(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+
+        self
+    }
+
+    /// Draw the border with plain ASCII, for terminals without box drawing
+    /// characters
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+
+        self
+    }
+
+    /// Use the supplied key bindings, see [`KeyMap`]
+    pub fn with_keymap(mut self, keymap: KeyMap) -> Self {
+        self.keymap = keymap;
+
+        self
+    }
+
+    /// Capture the mouse while the table is shown.  Clicking a row moves the
+    /// cursor to its line command and clicking a legend entry acts as its
+    /// key.
+    pub fn with_mouse(mut self, mouse: bool) -> Self {
+        self.mouse = mouse;
+
+        self
+    }
+
+    /// Ids of the rows with a line command, and the command
+    pub fn get_commands(&self) -> Vec<(String, char)> {
+        self.rows
+            .iter()
+            .filter(|r| r.command != ' ')
+            .map(|r| (r.id.to_string(), r.command))
+            .collect()
+    }
+
+    /// Fit the viewport to a terminal, never larger than the table.  The
+    /// border is drawn on the column and row after the viewport.
+    pub(crate) fn fit_terminal(&mut self, columns: u16, rows: u16) {
+        let row = self.current_row();
+
+        self.viewport = Pos {
+            x: columns.saturating_sub(1).min(self.size.x).max(1),
+            y: rows.saturating_sub(1).min(self.size.y).max(1),
+        };
+
+        self.scroll = self.scroll.min(self.max_scroll());
+        match row {
+            Some(row) if self.page_rows() > 0 => self.focus_row(row),
+            _ => self.current_pos = self.current_pos.constrain(self.viewport),
+        }
+    }
+
+    /// Number of rows that fit above the message row
+    fn page_rows(&self) -> usize {
+        let height = self
+            .viewport
+            .y
+            .saturating_sub(1)
+            .saturating_sub(self.origin.y);
+
+        height.div_ceil(self.spacing) as usize
+    }
+
+    /// Number of rows shown
+    fn shown(&self) -> usize {
+        self.rows
+            .len()
+            .saturating_sub(self.scroll)
+            .min(self.page_rows())
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.rows.len().saturating_sub(self.page_rows())
+    }
+
+    pub(crate) fn scroll_by(&mut self, rows: isize) {
+        self.scroll = self
+            .scroll
+            .saturating_add_signed(rows)
+            .min(self.max_scroll());
+    }
+
+    /// Position of the line command field of a row shown
+    fn field_pos(&self, shown: usize) -> Pos {
+        Pos {
+            x: self.origin.x,
+            y: self.origin.y + shown as u16 * self.spacing,
+        }
+    }
+
+    /// Column of the cells of a column
+    fn cell_x(&self, column: usize) -> u16 {
+        self.origin.x
+            + 2
+            + self.columns[..column]
+                .iter()
+                .map(|c| c.width + 1)
+                .sum::<u16>()
+    }
+
+    /// Cell text cut to the column width
+    fn cell_text(&self, row: &TableRow, column: usize) -> String {
+        row.cells
+            .get(column)
+            .map(|c| {
+                c.chars()
+                    .take(self.columns[column].width as usize)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Index of the row shown at a screen position, the line command field or
+    /// the cells
+    pub(crate) fn row_at(&self, pos: Pos) -> Option<usize> {
+        let offset = pos.y.checked_sub(self.origin.y)?;
+        if !offset.is_multiple_of(self.spacing) || pos.x < self.origin.x {
+            return None;
+        }
+
+        let shown = (offset / self.spacing) as usize;
+        if shown >= self.shown() {
+            return None;
+        }
+
+        let index = self.scroll + shown;
+        let right = match self.columns.len().checked_sub(1) {
+            Some(last) => {
+                self.cell_x(last) + self.cell_text(&self.rows[index], last).chars().count() as u16
+            }
+            None => self.origin.x + 1,
+        };
+
+        (pos.x < right).then_some(index)
+    }
+
+    /// Index of the row with the cursor on its line command field
+    fn current_row(&self) -> Option<usize> {
+        (0..self.shown())
+            .find(|&shown| self.field_pos(shown) == self.current_pos)
+            .map(|shown| self.scroll + shown)
+    }
+
+    /// Move the cursor to the line command field of a row, scrolling it into
+    /// view
+    pub(crate) fn focus_row(&mut self, index: usize) {
+        if index < self.scroll {
+            self.scroll = index;
+        } else if index >= self.scroll + self.page_rows() {
+            self.scroll = index + 1 - self.page_rows();
+        }
+        self.current_pos = self.field_pos(index - self.scroll);
+    }
+
+    /// Legend entries on the bottom border with their column and action, the
+    /// scroll keys only when there are more rows than fit
+    fn legend(&self) -> Vec<(u16, String, Action)> {
+        let mut legend = Vec::new();
+
+        let mut x = 2;
+        for (action, text) in [
+            (Action::Abort, "Abort"),
+            (Action::Submit, "Submit"),
+            (Action::PrevPage, "Up"),
+            (Action::NextPage, "Down"),
+        ] {
+            if matches!(action, Action::PrevPage | Action::NextPage) && self.max_scroll() == 0 {
+                continue;
+            }
+            if let Some(key) = self.keymap.key(action) {
+                let text = format!(" {}={} ", key, text);
+                let width = text.chars().count() as u16;
+                legend.push((x, text, action));
+                // Entries are separated by a border character
+                x += width + 1;
+            }
+        }
+
+        legend
+    }
+
+    /// Action of the legend entry at a screen position
+    pub(crate) fn legend_action(&self, pos: Pos) -> Option<Action> {
+        if pos.y != self.viewport.y {
+            return None;
+        }
+
+        self.legend().into_iter().find_map(|(x, text, action)| {
+            (pos.x >= x && pos.x < x + text.chars().count() as u16).then_some(action)
+        })
+    }
+
+    pub fn display(&mut self, stdout: &mut Stdout) -> io::Result<()> {
+        // Clear dialog
+        stdout
+            .queue(cursor::MoveTo(self.viewport.x, self.viewport.y))?
+            .queue(terminal::Clear(ClearType::FromCursorUp))?;
+
+        if let Some(error_message) = &self.error_message {
+            stdout
+                .queue(cursor::MoveTo(
+                    (self.viewport.x / 2).saturating_sub(error_message.len() as u16 / 2),
+                    self.viewport.y.saturating_sub(1),
+                ))?
+                .queue(style::SetForegroundColor(self.theme.error))?
+                .queue(style::Print(error_message))?;
+        }
+
+        // Border
+        let (vertical, horizontal, corner) = shape::border_chars(self.ascii);
+        stdout.queue(style::SetForegroundColor(self.theme.border))?;
+        for y in 0..self.viewport.y {
+            stdout
+                .queue(cursor::MoveTo(self.viewport.x, y))?
+                .queue(style::Print(vertical))?;
+        }
+        stdout
+            .queue(cursor::MoveTo(0, self.viewport.y))?
+            .queue(style::Print(
+                horizontal.to_string().repeat(self.viewport.x as usize),
+            ))?
+            .queue(style::Print(corner))?;
+
+        for (x, text, _) in self.legend() {
+            stdout
+                .queue(cursor::MoveTo(x, self.viewport.y))?
+                .queue(style::Print(text))?;
+        }
+
+        if self.max_scroll() > 0 {
+            let position = format!(
+                "Row {} to {} of {}",
+                self.scroll + 1,
+                self.scroll + self.shown(),
+                self.rows.len()
+            );
+            stdout
+                .queue(cursor::MoveTo(
+                    self.viewport.x.saturating_sub(position.len() as u16 + 2),
+                    0,
+                ))?
+                .queue(style::Print(position))?;
+        }
+
+        stdout.queue(style::SetForegroundColor(self.theme.label))?;
+        if let Some(title) = &self.title {
+            stdout
+                .queue(cursor::MoveTo(2, 0))?
+                .queue(style::SetAttribute(style::Attribute::Bold))?
+                .queue(style::Print(title))?
+                .queue(style::SetAttribute(style::Attribute::NormalIntensity))?;
+        }
+
+        // Column headers on the row above the first row
+        if self.origin.y > 0 && self.columns.iter().any(|c| !c.title.is_empty()) {
+            stdout.queue(style::SetAttribute(style::Attribute::Bold))?;
+            for (i, column) in self.columns.iter().enumerate() {
+                let title: String = column.title.chars().take(column.width as usize).collect();
+                stdout
+                    .queue(cursor::MoveTo(self.cell_x(i), self.origin.y - 1))?
+                    .queue(style::Print(title))?;
+            }
+            stdout.queue(style::SetAttribute(style::Attribute::NormalIntensity))?;
+        }
+
+        for shown in 0..self.shown() {
+            let pos = self.field_pos(shown);
+            let row = &self.rows[self.scroll + shown];
+
+            stdout
+                .queue(cursor::MoveTo(pos.x, pos.y))?
+                .queue(style::SetForegroundColor(self.theme.field))?
+                .queue(style::SetAttribute(style::Attribute::Underlined))?
+                .queue(style::Print(row.command))?
+                .queue(style::SetAttribute(style::Attribute::NoUnderline))?
+                .queue(style::SetForegroundColor(self.theme.label))?;
+
+            for column in 0..self.columns.len() {
+                stdout
+                    .queue(cursor::MoveTo(self.cell_x(column), pos.y))?
+                    .queue(style::Print(self.cell_text(row, column)))?;
+            }
+        }
+
+        stdout.queue(cursor::MoveTo(self.current_pos.x, self.current_pos.y))?;
+        stdout.queue(cursor::SetCursorStyle::SteadyUnderScore)?;
+
+        stdout.flush()
+    }
+}
+
+impl Table {
+    pub fn event_handler(&mut self, event: &Event) -> io::Result<EventHandlerResult> {
+        if let Event::Key(_) = event {
+            self.error_message = None;
+        }
+
+        match event {
+            // Line commands are typed like field values
+            Event::Key(k)
+                if self.current_row().is_some()
+                    && k.modifiers.difference(KeyModifiers::SHIFT).is_empty() =>
+            {
+                match k.code {
+                    KeyCode::Backspace | KeyCode::Delete => self.key(' '),
+                    KeyCode::Char(c) => self.key(c),
+                    _ => return Ok(self.key_event(event)),
+                }
+            }
+            Event::Mouse(mouse) => return self.mouse_event(mouse),
+            _ => return Ok(self.key_event(event)),
+        }
+
+        Ok(EventHandlerResult::Handled(EventResult::None))
+    }
+
+    /// Keys are looked up in the key map
+    fn key_event(&mut self, event: &Event) -> EventHandlerResult {
+        match self.keymap.action(event) {
+            Some(action) => self.action(action),
+            None => EventHandlerResult::NotHandled,
+        }
+    }
+
+    pub(crate) fn action(&mut self, action: Action) -> EventHandlerResult {
+        match action {
+            Action::Abort => return EventHandlerResult::Handled(EventResult::Abort),
+            Action::Submit => return EventHandlerResult::Handled(EventResult::Submit),
+            Action::MoveLeft => self.move_event(KeyCode::Left),
+            Action::MoveRight => self.move_event(KeyCode::Right),
+            Action::MoveUp => self.move_event(KeyCode::Up),
+            Action::MoveDown => self.move_event(KeyCode::Down),
+            Action::PrevPage => self.scroll_by(-(self.page_rows() as isize)),
+            Action::NextPage => self.scroll_by(self.page_rows() as isize),
+            Action::NextField => self.next_input(),
+            Action::PrevField => self.prev_input(),
+            // Handled by the App, or not used by tables
+            _ => return EventHandlerResult::NotHandled,
+        }
+
+        EventHandlerResult::Handled(EventResult::None)
+    }
+
+    /// Clicking a row moves the cursor to its line command and clicking the
+    /// legend acts as the key shown.  The wheel scrolls the table.
+    fn mouse_event(&mut self, mouse: &MouseEvent) -> io::Result<EventHandlerResult> {
+        let pos = Pos {
+            x: mouse.column,
+            y: mouse.row,
+        };
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(action) = self.legend_action(pos) {
+                    return Ok(self.action(action));
+                }
+                if let Some(index) = self.row_at(pos) {
+                    self.focus_row(index);
+                }
+            }
+            MouseEventKind::ScrollDown => self.scroll_by(1),
+            MouseEventKind::ScrollUp => self.scroll_by(-1),
+            _ => return Ok(EventHandlerResult::NotHandled),
+        }
+
+        Ok(EventHandlerResult::Handled(EventResult::None))
+    }
+
+    /// Move the cursor, the table scrolls when moving up from the first row
+    /// shown or down from the last one
+    pub fn move_event(&mut self, code: KeyCode) {
+        let first = self.field_pos(0);
+        let last = self.field_pos(self.shown().saturating_sub(1));
+
+        match code {
+            KeyCode::Up if self.current_pos.y == first.y && self.scroll > 0 => {
+                self.scroll_by(-1);
+                return;
+            }
+            KeyCode::Down if self.current_pos.y == last.y && self.scroll < self.max_scroll() => {
+                self.scroll_by(1);
+                return;
+            }
+            _ => (),
+        }
+
+        self.current_pos = match code {
+            KeyCode::Left => Pos {
+                x: self.current_pos.x.saturating_sub(1),
+                y: self.current_pos.y,
+            },
+            KeyCode::Right => Pos {
+                x: self.current_pos.x + 1,
+                y: self.current_pos.y,
+            },
+            KeyCode::Up => Pos {
+                x: self.current_pos.x,
+                y: self.current_pos.y.saturating_sub(1),
+            },
+            KeyCode::Down => Pos {
+                x: self.current_pos.x,
+                y: self.current_pos.y + 1,
+            },
+            _ => self.current_pos,
+        }
+        .constrain(self.viewport)
+    }
+
+    /// Enter a line command on the row with the cursor, a blank clears it.
+    /// Commands are not case sensitive.
+    pub fn key(&mut self, key: char) {
+        let Some(index) = self.current_row() else {
+            debug!("Not on a line command: {:?}", self.current_pos);
+            return;
+        };
+
+        let command = key.to_ascii_uppercase();
+        if command != ' ' && !self.commands.is_empty() && !self.commands.contains(&command) {
+            self.error_message = Some(format!("Invalid line command: {}", key));
+            return;
+        }
+
+        self.rows[index].command = command;
+    }
+
+    /// Line command field following the cursor on screen, wrapping around to
+    /// the first row shown
+    pub(crate) fn find_next_input(&mut self) -> Option<Pos> {
+        let key = |pos: Pos| (pos.y, pos.x);
+        let fields = (0..self.shown()).map(|shown| self.field_pos(shown));
+
+        fields
+            .clone()
+            .find(|pos| key(*pos) > key(self.current_pos))
+            .or_else(|| fields.clone().next())
+    }
+
+    /// Line command field preceding the cursor on screen, wrapping around to
+    /// the last row shown
+    pub(crate) fn find_prev_input(&mut self) -> Option<Pos> {
+        let key = |pos: Pos| (pos.y, pos.x);
+        let fields = (0..self.shown()).map(|shown| self.field_pos(shown));
+
+        fields
+            .clone()
+            .rfind(|pos| key(*pos) < key(self.current_pos))
+            .or_else(|| fields.clone().next_back())
+    }
+
+    pub fn next_input(&mut self) {
+        if let Some(pos) = self.find_next_input() {
+            self.current_pos = pos;
+        }
+    }
+
+    pub fn prev_input(&mut self) {
+        if let Some(pos) = self.find_prev_input() {
+            self.current_pos = pos;
+        }
+    }
+}
+
+pub struct TableBuilder {
+    pub title: Option<String>,
+    pub columns: Vec<Column>,
+    pub rows: Vec<TableRow>,
+    pub commands: Vec<char>,
+    origin: Pos,
+    spacing: u16,
+    pub size: Pos,
+}
+
+impl TableBuilder {
+    /// Title shown in the top left corner
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+
+        self
+    }
+
+    /// Add a column, the header is shown above the first row
+    pub fn with_column(mut self, title: impl Into<String>, width: u16) -> Self {
+        self.columns.push(Column {
+            title: title.into(),
+            width,
+        });
+
+        self
+    }
+
+    pub fn with_row(
+        mut self,
+        id: impl Into<String>,
+        cells: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.rows.push(TableRow::new(id, cells));
+
+        self
+    }
+
+    pub fn with_rows(mut self, rows: impl IntoIterator<Item = TableRow>) -> Self {
+        self.rows.extend(rows);
+
+        self
+    }
+
+    /// Add the rows of a data source
+    pub fn with_source(self, source: &dyn TableSource) -> Self {
+        self.with_rows(source.rows())
+    }
+
+    /// Line commands accepted, any command is accepted when empty
+    pub fn with_commands(mut self, commands: &str) -> Self {
+        self.commands = commands.chars().map(|c| c.to_ascii_uppercase()).collect();
+
+        self
+    }
+
+    /// Line command field of the first row and the screen rows per table row
+    pub(crate) fn with_layout(mut self, origin: impl Into<Pos>, spacing: u16) -> Self {
+        self.origin = origin.into();
+        self.spacing = spacing.max(1);
+
+        self
+    }
+
+    pub fn build(self) -> Table {
+        Table {
+            title: self.title,
+            columns: self.columns,
+            rows: self.rows,
+            commands: self.commands,
+            origin: self.origin,
+            spacing: self.spacing,
+            scroll: 0,
+            current_pos: self.origin,
+            size: self.size,
+            viewport: self.size,
+            error_message: None,
+            theme: Theme::from_env(),
+            keymap: KeyMap::default(),
+            mouse: false,
+            ascii: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Members(usize);
+
+    impl TableSource for Members {
+        fn rows(&self) -> Vec<TableRow> {
+            (1..=self.0)
+                .map(|i| TableRow::new(i.to_string(), [format!("MEMBER{:02}", i)]))
+                .collect()
+        }
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(code.into())
+    }
+
+    #[test]
+    fn commands() {
+        let mut table = Table::builder((80, 24))
+            .with_column("Name", 8)
+            .with_source(&Members(3))
+            .build();
+        assert_eq!(table.current_pos, (2, 3).into());
+
+        table.event_handler(&key(KeyCode::Char('s'))).unwrap();
+        table.next_input();
+        table.next_input();
+        table.event_handler(&key(KeyCode::Char('x'))).unwrap();
+        assert_eq!(
+            table.error_message.as_deref(),
+            Some("Invalid line command: x")
+        );
+        table.event_handler(&key(KeyCode::Char('D'))).unwrap();
+        assert_eq!(table.error_message, None);
+
+        // Not on a line command
+        table.move_event(KeyCode::Right);
+        table.event_handler(&key(KeyCode::Char('e'))).unwrap();
+
+        assert_eq!(
+            table.event_handler(&key(KeyCode::Enter)).unwrap(),
+            EventHandlerResult::Handled(EventResult::Submit)
+        );
+        assert_eq!(
+            table.get_commands(),
+            vec![("1".to_string(), 'S'), ("3".to_string(), 'D')]
+        );
+
+        table.prev_input();
+        table.event_handler(&key(KeyCode::Delete)).unwrap();
+        assert_eq!(table.get_commands(), vec![("1".to_string(), 'S')]);
+    }
+
+    #[test]
+    fn scroll() {
+        let mut table = Table::builder((80, 24))
+            .with_column("Name", 8)
+            .with_source(&Members(45))
+            .build();
+        assert_eq!(table.page_rows(), 20);
+
+        table.event_handler(&key(KeyCode::F(8))).unwrap();
+        assert_eq!(table.scroll, 20);
+        table.event_handler(&key(KeyCode::PageDown)).unwrap();
+        assert_eq!(table.scroll, 25);
+        assert_eq!(table.shown(), 20);
+
+        // Line commands go on the rows shown
+        table.event_handler(&key(KeyCode::Char('e'))).unwrap();
+        assert_eq!(table.get_commands(), vec![("26".to_string(), 'E')]);
+
+        table.event_handler(&key(KeyCode::Up)).unwrap();
+        assert_eq!(table.scroll, 24);
+        assert_eq!(table.current_pos, (2, 3).into());
+
+        table.event_handler(&key(KeyCode::F(7))).unwrap();
+        assert_eq!(table.scroll, 4);
+        table.event_handler(&key(KeyCode::F(7))).unwrap();
+        assert_eq!(table.scroll, 0);
+
+        table.focus_row(30);
+        assert_eq!(table.scroll, 11);
+        assert_eq!(table.current_pos, (2, 22).into());
+        table.event_handler(&key(KeyCode::Down)).unwrap();
+        assert_eq!(table.scroll, 12);
+    }
+
+    #[test]
+    fn fit_terminal() {
+        let mut table = Table::builder((80, 24))
+            .with_column("Name", 8)
+            .with_source(&Members(45))
+            .build();
+        table.focus_row(15);
+        assert_eq!(table.current_pos, (2, 18).into());
+
+        // The cursor stays on its row as the table shrinks
+        table.fit_terminal(60, 16);
+        assert_eq!(table.viewport, (59, 15).into());
+        assert_eq!(table.page_rows(), 11);
+        assert_eq!(table.scroll, 5);
+        assert_eq!(table.current_row(), Some(15));
+
+        // Never larger than the table
+        table.fit_terminal(200, 100);
+        assert_eq!(table.viewport, (80, 24).into());
+        assert_eq!(table.page_rows(), 20);
+        assert_eq!(table.current_row(), Some(15));
+
+        table.fit_terminal(0, 0);
+        assert_eq!(table.viewport, (1, 1).into());
+    }
+
+    #[test]
+    fn keymap() {
+        let mut table = Table::builder((80, 24))
+            .with_column("Name", 8)
+            .with_source(&Members(3))
+            .build()
+            .with_keymap(KeyMap::newline().with_binding(KeyCode::F(3), Action::Abort));
+
+        // Enter moves to the next row with the newline preset
+        assert_eq!(
+            table.event_handler(&key(KeyCode::Enter)).unwrap(),
+            EventHandlerResult::Handled(EventResult::None)
+        );
+        assert_eq!(table.current_pos, (2, 4).into());
+
+        // Uppercase letters are typed with shift
+        table
+            .event_handler(&Event::Key(crossterm::event::KeyEvent::new(
+                KeyCode::Char('S'),
+                KeyModifiers::SHIFT,
+            )))
+            .unwrap();
+        assert_eq!(table.get_commands(), vec![("2".to_string(), 'S')]);
+
+        assert_eq!(
+            table.event_handler(&key(KeyCode::F(3))).unwrap(),
+            EventHandlerResult::Handled(EventResult::Abort)
+        );
+    }
+
+    #[test]
+    fn layout() {
+        let table = Table::builder((80, 24))
+            .with_column("Name", 4)
+            .with_column("Size", 6)
+            .with_row("a", ["ALPHA", "10"])
+            .build();
+
+        assert_eq!(table.cell_x(1), 9);
+        assert_eq!(table.cell_text(&table.rows[0], 0), "ALPH");
+        assert_eq!(table.row_at((10, 3).into()), Some(0));
+        assert_eq!(table.row_at((11, 3).into()), None);
+        assert_eq!(table.row_at((2, 4).into()), None);
+        assert_eq!(table.legend_action((4, 24).into()), Some(Action::Abort));
+        assert_eq!(table.legend_action((30, 24).into()), None);
+    }
+}
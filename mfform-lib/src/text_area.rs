@@ -0,0 +1,135 @@
+/// A single wrapped row of a text area, as char indexes into the value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Row {
+    pub start: usize,
+    pub len: usize,
+}
+
+/// Word wrap `value` into rows no wider than `width`.
+///
+/// Hard line breaks ('\n') always start a new row, the break itself and the
+/// space a row was wrapped at are not part of any row.  There is always at
+/// least one row, even for an empty value.
+pub(crate) fn wrap(value: &str, width: usize) -> Vec<Row> {
+    let width = width.max(1);
+    let chars: Vec<char> = value.chars().collect();
+    let mut rows = Vec::new();
+
+    let mut start = 0;
+    loop {
+        let line_end = chars[start..]
+            .iter()
+            .position(|c| *c == '\n')
+            .map(|p| start + p)
+            .unwrap_or(chars.len());
+
+        let mut row_start = start;
+        while line_end - row_start > width {
+            // Wrap at the last space, a space just past the edge is fine too
+            match chars[row_start..=row_start + width]
+                .iter()
+                .rposition(|c| *c == ' ')
+            {
+                Some(p) if p > 0 => {
+                    rows.push(Row {
+                        start: row_start,
+                        len: p,
+                    });
+                    row_start += p + 1;
+                }
+                _ => {
+                    rows.push(Row {
+                        start: row_start,
+                        len: width,
+                    });
+                    row_start += width;
+                }
+            }
+        }
+        rows.push(Row {
+            start: row_start,
+            len: line_end - row_start,
+        });
+
+        if line_end >= chars.len() {
+            break;
+        }
+        start = line_end + 1;
+    }
+
+    rows
+}
+
+/// Char index of a row/column position, positions past the end of a row
+/// are moved to the end of that row.
+pub(crate) fn index(rows: &[Row], value_len: usize, row: usize, col: usize) -> usize {
+    match rows.get(row) {
+        Some(r) => r.start + col.min(r.len),
+        None => value_len,
+    }
+}
+
+/// Row/column position of a char index
+pub(crate) fn position(rows: &[Row], index: usize) -> (usize, usize) {
+    let row = rows
+        .iter()
+        .rposition(|r| r.start <= index)
+        .unwrap_or_default();
+
+    (row, (index - rows[row].start).min(rows[row].len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(value: &str, rows: &[Row]) -> Vec<String> {
+        rows.iter()
+            .map(|r| value.chars().skip(r.start).take(r.len).collect())
+            .collect()
+    }
+
+    #[test]
+    fn wrap_words() {
+        let value = "the quick brown fox jumps";
+        let rows = wrap(value, 10);
+
+        assert_eq!(texts(value, &rows), vec!["the quick", "brown fox", "jumps"]);
+    }
+
+    #[test]
+    fn wrap_long_word() {
+        let value = "abcdefghijkl mn";
+        let rows = wrap(value, 5);
+
+        assert_eq!(texts(value, &rows), vec!["abcde", "fghij", "kl mn"]);
+    }
+
+    #[test]
+    fn wrap_newlines() {
+        let value = "first\n\nþriðja";
+        let rows = wrap(value, 10);
+
+        assert_eq!(texts(value, &rows), vec!["first", "", "þriðja"]);
+    }
+
+    #[test]
+    fn wrap_empty() {
+        assert_eq!(wrap("", 10), vec![Row { start: 0, len: 0 }]);
+    }
+
+    #[test]
+    fn index_and_position() {
+        let value = "the quick brown fox";
+        let len = value.chars().count();
+        let rows = wrap(value, 10);
+
+        assert_eq!(index(&rows, len, 1, 2), 12);
+        assert_eq!(index(&rows, len, 1, 20), 19);
+        assert_eq!(index(&rows, len, 5, 0), 19);
+
+        assert_eq!(position(&rows, 12), (1, 2));
+        assert_eq!(position(&rows, 9), (0, 9));
+        assert_eq!(position(&rows, 19), (1, 9));
+    }
+}
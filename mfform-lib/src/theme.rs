@@ -0,0 +1,152 @@
+use crossterm::style::Color;
+
+/// Environment variable used to select a theme by name
+pub const THEME_ENV: &str = "MFFORM_THEME";
+
+/// Colours used when drawing forms.
+///
+/// `Color::Reset` leaves the terminal's own foreground colour in place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub label: Color,
+    pub field: Color,
+    pub field_changed: Color,
+    pub border: Color,
+    pub error: Color,
+    /// Field hint on the status row
+    pub hint: Color,
+    /// Placeholder text in empty fields, also shown dimmed
+    pub placeholder: Color,
+    /// Honour COLOR= attributes on labels and fields
+    pub attribute_colors: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::color_3279()
+    }
+}
+
+impl Theme {
+    /// Green phosphor, everything in shades of green.
+    pub fn classic() -> Self {
+        Self {
+            label: Color::Green,
+            field: Color::DarkGreen,
+            field_changed: Color::Green,
+            border: Color::DarkGreen,
+            error: Color::Green,
+            hint: Color::Green,
+            placeholder: Color::DarkGreen,
+            attribute_colors: false,
+        }
+    }
+
+    /// 3279 colour display, white labels, green fields and red modified data.
+    pub fn color_3279() -> Self {
+        Self {
+            label: Color::White,
+            field: Color::DarkGreen,
+            field_changed: Color::DarkRed,
+            border: Color::DarkGreen,
+            error: Color::DarkRed,
+            hint: Color::Cyan,
+            placeholder: Color::DarkGrey,
+            attribute_colors: true,
+        }
+    }
+
+    /// Dark colours, readable on terminals with a light background.
+    pub fn light() -> Self {
+        Self {
+            label: Color::Black,
+            field: Color::DarkBlue,
+            field_changed: Color::DarkRed,
+            border: Color::DarkBlue,
+            error: Color::DarkRed,
+            hint: Color::DarkCyan,
+            placeholder: Color::Grey,
+            attribute_colors: true,
+        }
+    }
+
+    /// No colours at all, only highlighting attributes are used.
+    pub fn monochrome() -> Self {
+        Self {
+            label: Color::Reset,
+            field: Color::Reset,
+            field_changed: Color::Reset,
+            border: Color::Reset,
+            error: Color::Reset,
+            hint: Color::Reset,
+            placeholder: Color::Reset,
+            attribute_colors: false,
+        }
+    }
+
+    /// Look up a theme by name, classic, 3279, light or monochrome.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "classic" | "green" => Some(Self::classic()),
+            "3279" | "color" | "colour" => Some(Self::color_3279()),
+            "light" => Some(Self::light()),
+            "monochrome" | "mono" => Some(Self::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// Theme from the environment.
+    ///
+    /// A theme named in `MFFORM_THEME` is used if valid, otherwise a non-empty
+    /// `NO_COLOR` selects the monochrome theme (see <https://no-color.org>).
+    pub fn from_env() -> Self {
+        if let Some(theme) = std::env::var(THEME_ENV)
+            .ok()
+            .and_then(|name| Self::from_name(&name))
+        {
+            return theme;
+        }
+
+        match std::env::var_os("NO_COLOR") {
+            Some(no_color) if !no_color.is_empty() => Self::monochrome(),
+            _ => Self::default(),
+        }
+    }
+
+    /// Colour for a label or field, honouring the COLOR= attribute if the
+    /// theme allows it.
+    pub(crate) fn attribute_color(
+        &self,
+        attribute: Option<crate::attribute::Color>,
+        default: Color,
+    ) -> Color {
+        match attribute {
+            Some(color) if self.attribute_colors => color.into(),
+            _ => default,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_name() {
+        assert_eq!(Theme::from_name("classic"), Some(Theme::classic()));
+        assert_eq!(Theme::from_name("3279"), Some(Theme::color_3279()));
+        assert_eq!(Theme::from_name("Light"), Some(Theme::light()));
+        assert_eq!(Theme::from_name("MONOCHROME"), Some(Theme::monochrome()));
+        assert_eq!(Theme::from_name("nope"), None);
+    }
+
+    #[test]
+    fn monochrome_ignores_attribute_colors() {
+        let theme = Theme::monochrome();
+
+        assert_eq!(
+            theme.attribute_color(Some(crate::attribute::Color::Red), theme.field),
+            Color::Reset
+        );
+    }
+}
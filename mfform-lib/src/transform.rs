@@ -0,0 +1,81 @@
+/// Translation applied to a field value, either as characters are typed or
+/// when the value is output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    /// Translate lower case to upper case as characters are typed
+    Upper,
+    /// Translate upper case to lower case as characters are typed
+    Lower,
+    /// Remove leading and trailing whitespace on output
+    Trim,
+    /// Replace runs of whitespace with a single space on output
+    Collapse,
+}
+
+impl Transform {
+    /// Translate a typed character, characters without a single character
+    /// translation (e.g. 'ß') are left as is.
+    pub fn key(&self, c: char) -> char {
+        let mut translated = match self {
+            Transform::Upper => c.to_uppercase().collect::<Vec<_>>(),
+            Transform::Lower => c.to_lowercase().collect::<Vec<_>>(),
+            Transform::Trim | Transform::Collapse => return c,
+        };
+
+        match translated.len() {
+            1 => translated.remove(0),
+            _ => c,
+        }
+    }
+
+    /// Translate a value for output
+    pub fn output(&self, value: &str) -> String {
+        match self {
+            Transform::Upper => value.to_uppercase(),
+            Transform::Lower => value.to_lowercase(),
+            Transform::Trim => value.trim().to_string(),
+            Transform::Collapse => {
+                let mut output = String::with_capacity(value.len());
+                let mut space = false;
+
+                for c in value.chars() {
+                    if c.is_whitespace() {
+                        space = true;
+                        continue;
+                    }
+                    if space {
+                        output.push(' ');
+                        space = false;
+                    }
+                    output.push(c);
+                }
+                if space {
+                    output.push(' ');
+                }
+
+                output
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        assert_eq!(Transform::Upper.key('a'), 'A');
+        assert_eq!(Transform::Upper.key('þ'), 'Þ');
+        assert_eq!(Transform::Upper.key('ß'), 'ß');
+        assert_eq!(Transform::Lower.key('Ö'), 'ö');
+        assert_eq!(Transform::Trim.key('a'), 'a');
+    }
+
+    #[test]
+    fn output() {
+        assert_eq!(Transform::Upper.output("sys1.proclib"), "SYS1.PROCLIB");
+        assert_eq!(Transform::Trim.output("  a b  "), "a b");
+        assert_eq!(Transform::Collapse.output("  a \t b\n\nc"), " a b c");
+    }
+}
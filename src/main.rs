@@ -5,7 +5,7 @@ use std::{
 
 mod parser;
 
-use mfform_lib::{App, EventResult, Form, Pos};
+use mfform_lib::{App, EventResult, Form, KeyMap, Pos, Theme};
 
 pub fn form_from_textfile(input_file: impl AsRef<OsStr>, size: impl Into<Pos>) -> io::Result<Form> {
     let mut form = Form::new(size)?;
@@ -19,12 +19,12 @@ pub fn form_from_textfile(input_file: impl AsRef<OsStr>, size: impl Into<Pos>) -
             break;
         }
 
-        if line.len() > 5 {
-            crate::parser::parse_str(&mut form, line.trim())
-                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        if !line.trim().is_empty() {
+            crate::parser::parse_str(&mut form, line.trim()).map_err(io::Error::other)?;
         }
         line.clear();
     }
+    crate::parser::parse_finish(&form).map_err(io::Error::other)?;
 
     let form = form.place_cursor();
 
@@ -34,28 +34,87 @@ pub fn form_from_textfile(input_file: impl AsRef<OsStr>, size: impl Into<Pos>) -
 fn main() -> io::Result<()> {
     let stdout = io::stdout();
 
-    let mut args = std::env::args_os();
-    let screen_name = if args.len() > 1 {
-        let _ = args.next().unwrap();
-        args.next().unwrap()
-    } else {
-        OsString::from("screen.mfform")
-    };
+    let mut screen_name = OsString::from("screen.mfform");
+    let mut theme = None;
+    let mut autoskip = false;
+    let mut readline = false;
+    let mut paste_spill = false;
+    let mut mouse = false;
+    let mut ascii = false;
+    let mut keymap = None;
+    let mut json = false;
+
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--theme" {
+            let name = args.next().unwrap_or_default();
+            theme = Some(Theme::from_name(&name.to_string_lossy()).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Unknown theme: {}", name.to_string_lossy()),
+                )
+            })?);
+        } else if arg == "--autoskip" {
+            autoskip = true;
+        } else if arg == "--readline" {
+            readline = true;
+        } else if arg == "--paste-spill" {
+            paste_spill = true;
+        } else if arg == "--mouse" {
+            mouse = true;
+        } else if arg == "--ascii" {
+            ascii = true;
+        } else if arg == "--format" {
+            let format = args.next().unwrap_or_default();
+            json = match format.to_string_lossy().as_ref() {
+                "flat" => false,
+                "json" => true,
+                format => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("Unknown output format: {}", format),
+                    ))
+                }
+            };
+        } else if arg == "--keymap" {
+            // A preset name, or a key map file
+            let name = args.next().unwrap_or_default();
+            keymap = Some(match KeyMap::preset(&name.to_string_lossy()) {
+                Some(keymap) => keymap,
+                None => KeyMap::load(&name)?,
+            });
+        } else {
+            screen_name = arg;
+        }
+    }
 
     let mut app = App::with_writer(stdout);
     app.init()?;
 
     let mut form = form_from_textfile(screen_name, (82, 24))?;
+    if let Some(theme) = theme {
+        form.set_theme(theme);
+    }
+    form.set_autoskip(autoskip);
+    form.set_readline(readline);
+    form.set_paste_spill(paste_spill);
+    form.set_mouse(mouse);
+    form.set_ascii(ascii);
+    if let Some(keymap) = keymap {
+        form.set_keymap(keymap);
+    }
 
     let result = app.execute(&mut form)?;
 
     drop(app);
 
-    if result == EventResult::Submit {
+    if result == EventResult::Submit && json {
+        println!("{}", form.get_json());
+    } else if result == EventResult::Submit {
         let fields = form.get_field_and_data();
 
         for (name, value) in fields {
-            println!("{}={}", name, snailquote::escape(value));
+            println!("{}={}", name, snailquote::escape(&value));
         }
     }
 
@@ -1,11 +1,15 @@
-use mfform_lib::{Form, Input, Label};
+use mfform_lib::{
+    Color, DateFormat, Form, Format, Group, GroupBuilder, Highlight, Input, InputBuilder, Label,
+    LabelBuilder, Mask, NumberFormat, Shape, ShapeBuilder, TimeFormat, Transform,
+};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{alpha1, alphanumeric1, multispace0, multispace1, u16},
-    combinator::recognize,
-    multi::many0_count,
-    sequence::{pair, tuple},
+    bytes::complete::{tag, take_till1},
+    character::complete::{alpha1, alphanumeric1, multispace0, multispace1, u16, u8},
+    combinator::{cut, eof, map, map_res, opt, peek, recognize, value},
+    multi::{many0, many0_count},
+    number::complete::double,
+    sequence::{pair, preceded, terminated, tuple},
     IResult,
 };
 
@@ -16,19 +20,290 @@ pub fn identifier(input: &str) -> IResult<&str, &str> {
     ))(input)
 }
 
+/// Attribute options, e.g. COLOR=RED HILIGHT=REVERSE
+#[derive(Debug, Clone, PartialEq)]
+enum WidgetOption {
+    Color(Color),
+    Highlight(Highlight),
+    MaxLength(u16),
+    Picture(Mask),
+    RawOutput,
+    Decimals(u8),
+    Signed,
+    Thousands,
+    ZeroFill,
+    Min(f64),
+    Max(f64),
+    Transform(Transform),
+    Autoskip(bool),
+    TabIndex(u16),
+    TabStop(bool),
+    InitialCursor,
+    Output(bool),
+    Visible(u16),
+}
+
+fn parse_color(input: &str) -> IResult<&str, Color> {
+    alt((
+        value(Color::Blue, tag("BLUE")),
+        value(Color::Red, tag("RED")),
+        value(Color::Pink, tag("PINK")),
+        value(Color::Green, tag("GREEN")),
+        value(Color::Turquoise, tag("TURQUOISE")),
+        value(Color::Yellow, tag("YELLOW")),
+        value(Color::White, tag("WHITE")),
+    ))(input)
+}
+
+fn parse_highlight(input: &str) -> IResult<&str, Highlight> {
+    alt((
+        value(Highlight::Reverse, tag("REVERSE")),
+        value(Highlight::Blink, tag("BLINK")),
+        value(Highlight::Underscore, tag("UNDERSCORE")),
+        value(Highlight::Intensify, tag("INTENSIFY")),
+    ))(input)
+}
+
+fn parse_transform(input: &str) -> IResult<&str, Transform> {
+    alt((
+        value(Transform::Upper, flag("UPPER")),
+        value(Transform::Lower, flag("LOWER")),
+        value(Transform::Trim, flag("TRIM")),
+        value(Transform::Collapse, flag("COLLAPSE")),
+    ))(input)
+}
+
+fn parse_color_option(input: &str) -> IResult<&str, WidgetOption> {
+    map(preceded(tag("COLOR="), parse_color), WidgetOption::Color)(input)
+}
+
+fn parse_highlight_option(input: &str) -> IResult<&str, WidgetOption> {
+    map(
+        preceded(tag("HILIGHT="), parse_highlight),
+        WidgetOption::Highlight,
+    )(input)
+}
+
+fn parse_option(input: &str) -> IResult<&str, WidgetOption> {
+    alt((
+        parse_color_option,
+        parse_highlight_option,
+        map(preceded(tag("MAXLEN="), u16), WidgetOption::MaxLength),
+        preceded(
+            tag("PIC="),
+            cut(map_res(
+                take_till1(|c: char| c.is_whitespace()),
+                |picture| Mask::from_picture(picture).map(WidgetOption::Picture),
+            )),
+        ),
+        value(WidgetOption::RawOutput, flag("RAW")),
+        map(preceded(tag("DEC="), u8), WidgetOption::Decimals),
+        value(WidgetOption::Signed, flag("SIGNED")),
+        value(WidgetOption::Thousands, flag("THOUSANDS")),
+        value(WidgetOption::ZeroFill, flag("ZEROFILL")),
+        map(preceded(tag("MIN="), double), WidgetOption::Min),
+        map(preceded(tag("MAX="), double), WidgetOption::Max),
+        map(parse_transform, WidgetOption::Transform),
+        value(WidgetOption::Autoskip(true), flag("AUTOSKIP")),
+        value(WidgetOption::Autoskip(false), flag("NOSKIP")),
+        map(preceded(tag("TAB="), u16), WidgetOption::TabIndex),
+        value(WidgetOption::TabStop(false), flag("NOTAB")),
+        value(WidgetOption::InitialCursor, flag("IC")),
+        value(WidgetOption::Output(false), flag("NOOUTPUT")),
+    ))(input)
+}
+
+/// Option keyword without a value, must be followed by a space or end of line
+fn flag<'a>(name: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    terminated(tag(name), peek(alt((multispace1, eof))))
+}
+
+/// Options go between the widget keyword and the coordinates, so text after
+/// the name is never taken for an option.  Each option is followed by a space.
+fn parse_options(input: &str) -> IResult<&str, Vec<WidgetOption>> {
+    many0(terminated(parse_option, multispace1))(input)
+}
+
+fn parse_label_options(input: &str) -> IResult<&str, Vec<WidgetOption>> {
+    many0(terminated(
+        alt((parse_color_option, parse_highlight_option)),
+        multispace1,
+    ))(input)
+}
+
+fn parse_shape_options(input: &str) -> IResult<&str, Vec<WidgetOption>> {
+    many0(terminated(parse_color_option, multispace1))(input)
+}
+
+fn parse_group_options(input: &str) -> IResult<&str, Vec<WidgetOption>> {
+    many0(terminated(
+        alt((
+            map(preceded(tag("VISIBLE="), u16), WidgetOption::Visible),
+            map(preceded(tag("MAX="), double), WidgetOption::Max),
+        )),
+        multispace1,
+    ))(input)
+}
+
+fn label_options(builder: LabelBuilder, options: &[WidgetOption]) -> LabelBuilder {
+    options
+        .iter()
+        .fold(builder, |builder, option| match option {
+            WidgetOption::Color(c) => builder.with_color(*c),
+            WidgetOption::Highlight(h) => builder.with_highlight(*h),
+            _ => builder,
+        })
+}
+
+fn shape_options(builder: ShapeBuilder, options: &[WidgetOption]) -> ShapeBuilder {
+    options
+        .iter()
+        .fold(builder, |builder, option| match option {
+            WidgetOption::Color(c) => builder.with_color(*c),
+            _ => builder,
+        })
+}
+
+fn input_options(builder: InputBuilder, options: &[WidgetOption]) -> InputBuilder {
+    options
+        .iter()
+        .fold(builder, |builder, option| match option {
+            WidgetOption::Color(c) => builder.with_color(*c),
+            WidgetOption::Highlight(h) => builder.with_highlight(*h),
+            WidgetOption::MaxLength(l) => builder.with_max_length(*l),
+            WidgetOption::Picture(m) => builder.with_mask(m.clone()),
+            WidgetOption::RawOutput => builder.with_raw_output(true),
+            WidgetOption::Transform(t) => builder.with_transform(*t),
+            WidgetOption::Autoskip(a) => builder.with_autoskip(*a),
+            WidgetOption::TabIndex(i) => builder.with_tab_index(*i),
+            WidgetOption::TabStop(t) => builder.with_tab_stop(*t),
+            WidgetOption::InitialCursor => builder.with_initial_cursor(true),
+            WidgetOption::Output(o) => builder.with_output(*o),
+            _ => builder,
+        })
+}
+
+fn group_options(builder: GroupBuilder, options: &[WidgetOption]) -> GroupBuilder {
+    options
+        .iter()
+        .fold(builder, |builder, option| match option {
+            WidgetOption::Visible(v) => builder.with_visible(*v as usize),
+            WidgetOption::Max(m) => builder.with_max_rows(*m as usize),
+            _ => builder,
+        })
+}
+
+fn number_options(options: &[WidgetOption]) -> NumberFormat {
+    options
+        .iter()
+        .fold(NumberFormat::default(), |number, option| match option {
+            WidgetOption::Decimals(d) => NumberFormat {
+                decimals: *d,
+                ..number
+            },
+            WidgetOption::Signed => NumberFormat {
+                signed: true,
+                ..number
+            },
+            WidgetOption::Thousands => NumberFormat {
+                thousands: true,
+                ..number
+            },
+            WidgetOption::ZeroFill => NumberFormat {
+                zero_fill: true,
+                ..number
+            },
+            WidgetOption::Min(min) => NumberFormat {
+                min: Some(*min),
+                ..number
+            },
+            WidgetOption::Max(max) => NumberFormat {
+                max: Some(*max),
+                ..number
+            },
+            _ => number,
+        })
+}
+
 fn parse_label(input: &str) -> IResult<&str, Widget> {
-    let (rest, (_, x, _, y, _)) =
-        tuple((tag("LABEL "), u16, multispace1, u16, multispace1))(input)?;
+    // LABEL COLOR=WHITE 1 2 texti hér
 
-    Ok(("", Widget::Label(Label::new_label((x, y), rest))))
+    let (rest, (_, _, options, x, _, y, _)) = tuple((
+        tag("LABEL"),
+        multispace1,
+        parse_label_options,
+        u16,
+        multispace1,
+        u16,
+        multispace1,
+    ))(input)?;
+
+    Ok((
+        "",
+        Widget::Label(label_options(Label::builder((x, y), rest), &options).build()),
+    ))
+}
+
+fn parse_box(input: &str) -> IResult<&str, Widget> {
+    // BOX COLOR=BLUE 1 2 40 6 titill
+
+    let (rest, (_, _, options, x, _, y, _, width, _, height, _)) = tuple((
+        tag("BOX"),
+        multispace1,
+        parse_shape_options,
+        u16,
+        multispace1,
+        u16,
+        multispace1,
+        u16,
+        multispace1,
+        u16,
+        multispace0,
+    ))(input)?;
+
+    let mut builder = shape_options(Shape::frame((x, y), width, height), &options);
+    if !rest.is_empty() {
+        builder = builder.with_title(rest);
+    }
+
+    Ok(("", Widget::Shape(builder.build())))
+}
+
+fn parse_line(input: &str) -> IResult<&str, Widget> {
+    // HLINE COLOR=BLUE 1 2 40
+
+    let (rest, (widget_type, _, options, x, _, y, _, length, _, _)) = tuple((
+        alt((tag("HLINE"), tag("VLINE"))),
+        multispace1,
+        parse_shape_options,
+        u16,
+        multispace1,
+        u16,
+        multispace1,
+        u16,
+        multispace0,
+        eof,
+    ))(input)?;
+
+    let builder = match widget_type {
+        "HLINE" => Shape::hline((x, y), length),
+        "VLINE" => Shape::vline((x, y), length),
+        _ => unimplemented!(),
+    };
+
+    Ok((
+        rest,
+        Widget::Shape(shape_options(builder, &options).build()),
+    ))
 }
 
 fn parse_input(input: &str) -> IResult<&str, Widget> {
-    // INPUT 5 111 10 nafn texti hér
+    // INPUT UPPER 5 111 10 nafn texti hér
 
-    let (rest, (widget_type, _, x, _, y, _, length, _, name, _)) = tuple((
-        alt((tag("INPUT"), tag("PASSWORD"))),
+    let (rest, (widget_type, _, options, x, _, y, _, length, _, name, _)) = tuple((
+        alt((tag("INPUT"), tag("PASSWORD"), tag("OUTPUT"))),
         multispace1,
+        parse_options,
         u16,
         multispace1,
         u16,
@@ -39,21 +314,22 @@ fn parse_input(input: &str) -> IResult<&str, Widget> {
         multispace0,
     ))(input)?;
 
+    let builder = input_options(Input::builder((x, y), length, name), &options);
+
     match widget_type {
-        "INPUT" => Ok((
+        "INPUT" => Ok(("", Widget::Input(builder.with_value(rest).build()))),
+        "PASSWORD" => Ok((
             "",
-            Widget::Input(
-                Input::builder((x, y), length, name)
-                    .with_value(rest)
-                    .build(),
-            ),
+            Widget::Input(builder.with_value(rest).with_mask_char('*').build()),
         )),
-        "PASSWORD" => Ok((
+        "OUTPUT" => Ok((
             "",
             Widget::Input(
-                Input::builder((x, y), length, name)
+                builder
                     .with_value(rest)
-                    .with_mask_char('*')
+                    .with_default_value(rest)
+                    .with_protected(true)
+                    .with_tab_stop(false)
                     .build(),
             ),
         )),
@@ -61,6 +337,170 @@ fn parse_input(input: &str) -> IResult<&str, Widget> {
     }
 }
 
+fn parse_hidden(input: &str) -> IResult<&str, Widget> {
+    // HIDDEN nafn texti hér
+
+    let (rest, (_, _, name, _)) =
+        tuple((tag("HIDDEN"), multispace1, identifier, multispace0))(input)?;
+
+    Ok((
+        "",
+        Widget::Input(Input::hidden(name).with_value(rest).build()),
+    ))
+}
+
+fn parse_text_area(input: &str) -> IResult<&str, Widget> {
+    // TEXTAREA 5 11 40 4 nafn texti hér
+
+    let (rest, (_, _, options, x, _, y, _, length, _, height, _, name, _)) = tuple((
+        tag("TEXTAREA"),
+        multispace1,
+        parse_options,
+        u16,
+        multispace1,
+        u16,
+        multispace1,
+        u16,
+        multispace1,
+        u16,
+        multispace1,
+        identifier,
+        multispace0,
+    ))(input)?;
+
+    Ok((
+        "",
+        Widget::Input(
+            input_options(Input::builder((x, y), length, name), &options)
+                .with_height(height)
+                .with_value(rest)
+                .build(),
+        ),
+    ))
+}
+
+fn parse_checked(input: &str) -> Result<bool, nom::Err<nom::error::Error<&str>>> {
+    match input {
+        "" | "false" => Ok(false),
+        "true" => Ok(true),
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Tag,
+        ))),
+    }
+}
+
+fn parse_checkbox(input: &str) -> IResult<&str, Widget> {
+    // CHECKBOX 5 11 nafn true
+
+    let (rest, (_, _, options, x, _, y, _, name, _)) = tuple((
+        tag("CHECKBOX"),
+        multispace1,
+        parse_options,
+        u16,
+        multispace1,
+        u16,
+        multispace1,
+        identifier,
+        multispace0,
+    ))(input)?;
+
+    Ok((
+        "",
+        Widget::Input(
+            input_options(Input::checkbox((x, y), name), &options)
+                .with_checked(parse_checked(rest)?)
+                .build(),
+        ),
+    ))
+}
+
+fn parse_radio(input: &str) -> IResult<&str, Widget> {
+    // RADIO 5 11 nafn id true
+
+    let (rest, (_, _, options, x, _, y, _, name, _, id, _)) = tuple((
+        tag("RADIO"),
+        multispace1,
+        parse_options,
+        u16,
+        multispace1,
+        u16,
+        multispace1,
+        identifier,
+        multispace1,
+        identifier,
+        multispace0,
+    ))(input)?;
+
+    Ok((
+        "",
+        Widget::Input(
+            input_options(Input::radio((x, y), name, id), &options)
+                .with_checked(parse_checked(rest)?)
+                .build(),
+        ),
+    ))
+}
+
+fn parse_format(input: &str) -> IResult<&str, Format> {
+    alt((
+        value(Format::Date(DateFormat::Iso), tag("ISO")),
+        value(Format::Date(DateFormat::European), tag("EUR")),
+        value(Format::Date(DateFormat::Julian), tag("JULIAN")),
+        value(Format::Time(TimeFormat::HourMinuteSecond), tag("HHMMSS")),
+        value(Format::Time(TimeFormat::HourMinute), tag("HHMM")),
+    ))(input)
+}
+
+fn parse_date_time(input: &str) -> IResult<&str, Widget> {
+    // DATE 5 11 nafn EUR 24.12.2024
+    // TIME 5 11 nafn HHMM
+
+    let (rest, (widget_type, _, options, x, _, y, _, name, _, format, _)) = tuple((
+        alt((tag("DATE"), tag("TIME"))),
+        multispace1,
+        parse_options,
+        u16,
+        multispace1,
+        u16,
+        multispace1,
+        identifier,
+        multispace0,
+        opt(parse_format),
+        multispace0,
+    ))(input)?;
+
+    let format = match (widget_type, format) {
+        ("DATE", Some(f @ Format::Date(_))) | ("TIME", Some(f @ Format::Time(_))) => f,
+        ("DATE", None) => Format::Date(DateFormat::Iso),
+        ("TIME", None) => Format::Time(TimeFormat::HourMinute),
+        _ => {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Tag,
+            )));
+        }
+    };
+
+    if !rest.is_empty() && format.validate(rest).is_err() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            rest,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+
+    Ok((
+        "",
+        Widget::Input(
+            input_options(Input::builder((x, y), 0, name), &options)
+                .with_format(format)
+                .with_value(rest)
+                .with_default_value(rest)
+                .build(),
+        ),
+    ))
+}
+
 fn parse_select(input: &str) -> IResult<&str, Widget> {
     // SELECT input id display
 
@@ -79,12 +519,91 @@ fn parse_select(input: &str) -> IResult<&str, Widget> {
     ))
 }
 
+fn parse_help(input: &str) -> IResult<&str, Widget> {
+    // HELP input text, or HELP * text for the form
+
+    let (rest, (_widget_type, _, input, _)) = tuple((
+        tag("HELP"),
+        multispace1,
+        alt((identifier, tag("*"))),
+        multispace1,
+    ))(input)?;
+
+    let input = (input != "*").then(|| input.to_string());
+
+    Ok(("", Widget::Help(input, rest.to_string())))
+}
+
+fn parse_hint(input: &str) -> IResult<&str, Widget> {
+    // HINT input text, or PLACEHOLDER input text
+
+    let (rest, (widget_type, _, input, _)) = tuple((
+        alt((tag("HINT"), tag("PLACEHOLDER"))),
+        multispace1,
+        identifier,
+        multispace1,
+    ))(input)?;
+
+    match widget_type {
+        "HINT" => Ok(("", Widget::Hint(input.to_string(), rest.to_string()))),
+        "PLACEHOLDER" => Ok(("", Widget::Placeholder(input.to_string(), rest.to_string()))),
+        _ => unimplemented!(),
+    }
+}
+
+fn parse_page(input: &str) -> IResult<&str, Widget> {
+    // PAGE, the widgets that follow go on a new page
+
+    let (rest, _) = terminated(tag("PAGE"), eof)(input)?;
+
+    Ok((rest, Widget::Page))
+}
+
+fn parse_group(input: &str) -> IResult<&str, Widget> {
+    // GROUP VISIBLE=3 MAX=20 item 5, the fields up to END are the first row
+
+    let (rest, (_widget_type, _, options, name, _, rows, _)) = tuple((
+        tag("GROUP"),
+        multispace1,
+        parse_group_options,
+        identifier,
+        multispace1,
+        u16,
+        multispace0,
+    ))(input)?;
+    let (rest, _) = eof(rest)?;
+
+    Ok((
+        rest,
+        Widget::Group(group_options(Group::builder(name, rows as usize), &options).build()),
+    ))
+}
+
+fn parse_end(input: &str) -> IResult<&str, Widget> {
+    // END of a group
+
+    let (rest, _) = terminated(tag("END"), eof)(input)?;
+
+    Ok((rest, Widget::End))
+}
+
+fn parse_size(input: &str) -> IResult<&str, Widget> {
+    // SIZE 82 60
+
+    let (rest, (_widget_type, _, x, _, y)) =
+        tuple((tag("SIZE"), multispace1, u16, multispace1, u16))(input)?;
+    let (rest, _) = eof(rest)?;
+
+    Ok((rest, Widget::Size(x, y)))
+}
+
 fn parse_number(input: &str) -> IResult<&str, Widget> {
-    // INPUT 5 111 10 nafn texti hér
+    // NUMBER SIGNED DEC=2 5 11 10 nafn -12.50
 
-    let (rest, (widget_type, _, x, _, y, _, length, _, name, _)) = tuple((
+    let (rest, (widget_type, _, options, x, _, y, _, length, _, name, _)) = tuple((
         tag("NUMBER"),
         multispace1,
+        parse_options,
         u16,
         multispace1,
         u16,
@@ -95,28 +614,23 @@ fn parse_number(input: &str) -> IResult<&str, Widget> {
         multispace0,
     ))(input)?;
 
-    let default_value = if !rest.is_empty() {
-        match str::parse::<i64>(rest) {
-            Ok(i) => i.to_string(),
-            Err(_) => {
-                return Err(nom::Err::Error(nom::error::Error::new(
-                    rest,
-                    nom::error::ErrorKind::Digit,
-                )));
-            }
-        }
-    } else {
-        String::new()
-    };
+    let format = Format::Number(number_options(&options));
+
+    if format.validate(rest).is_err() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            rest,
+            nom::error::ErrorKind::Digit,
+        )));
+    }
 
     match widget_type {
         "NUMBER" => Ok((
             "",
             Widget::Input(
-                Input::builder((x, y), length, name)
-                    .with_value(&default_value)
-                    .with_default_value(default_value)
-                    .with_allowed_characters('0'..='9')
+                input_options(Input::builder((x, y), length, name), &options)
+                    .with_format(format)
+                    .with_value(rest)
+                    .with_default_value(rest)
                     .build(),
             ),
         )),
@@ -124,15 +638,44 @@ fn parse_number(input: &str) -> IResult<&str, Widget> {
     }
 }
 
+// Widgets are added to the form right after parsing
+#[allow(clippy::large_enum_variant)]
 enum Widget {
     Label(Label),
     Input(Input),
     Select(String, String, String),
+    /// Help for an input, or for the form
+    Help(Option<String>, String),
+    Hint(String, String),
+    Shape(Shape),
+    Placeholder(String, String),
+    Page,
+    Size(u16, u16),
+    Group(Group),
+    End,
 }
 
 fn parse_widget(input: &str) -> Result<Widget, String> {
-    let (_, widget) = alt((parse_label, parse_input, parse_number, parse_select))(input)
-        .map_err(|e| e.to_string())?;
+    let (_, widget) = alt((
+        parse_label,
+        parse_input,
+        parse_hidden,
+        parse_number,
+        parse_text_area,
+        parse_checkbox,
+        parse_radio,
+        parse_date_time,
+        parse_select,
+        parse_help,
+        parse_hint,
+        parse_box,
+        parse_line,
+        parse_page,
+        parse_size,
+        parse_group,
+        parse_end,
+    ))(input)
+    .map_err(|e| e.to_string())?;
 
     Ok(widget)
 }
@@ -143,16 +686,39 @@ pub fn parse_str(form: &mut Form, input: &str) -> Result<(), String> {
     match widget {
         Widget::Label(l) => form.add_label(l),
         Widget::Input(i) => form.add_input(i),
-        Widget::Select(input, id, text) => form.add_select(input, id, text),
+        Widget::Select(input, id, text) => form.add_select(input, id, text)?,
+        Widget::Help(Some(input), text) => form.add_help(&input, &text)?,
+        Widget::Help(None, text) => {
+            let help = match form.help() {
+                Some(help) => format!("{}\n{}", help, text),
+                None => text,
+            };
+            form.set_help(help);
+        }
+        Widget::Hint(input, text) => form.set_hint(&input, text)?,
+        Widget::Shape(s) => form.add_shape(s),
+        Widget::Placeholder(input, text) => form.set_placeholder(&input, text)?,
+        Widget::Page => form.new_page(),
+        Widget::Size(x, y) => form.set_size((x, y)),
+        Widget::Group(g) => form.begin_group(g)?,
+        Widget::End => form.end_group()?,
     }
 
     Ok(())
 }
 
+/// Check the form once all lines are parsed
+pub fn parse_finish(form: &Form) -> Result<(), String> {
+    match form.open_group() {
+        Some(group) => Err(format!("GROUP {} without END", group.name)),
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::input::Select;
+    use mfform_lib::{InputKind, Select};
 
     #[test]
     fn test_parse_label() {
@@ -164,6 +730,55 @@ mod tests {
         assert_eq!(label.text, "texti hér");
     }
 
+    #[test]
+    fn test_parse_keyword_text() {
+        // Text after the coordinates or the name is never taken for options
+        for text in [
+            "IC number",
+            "SIGNED amount",
+            "UPPER case letters",
+            "MAX=5 items",
+            "COLOR=RED alert",
+        ] {
+            let Widget::Label(label) = parse_widget(&format!("LABEL 0 0 {}", text)).unwrap() else {
+                panic!("Parsed value is not a label");
+            };
+            assert_eq!(label.text, text);
+            assert_eq!(label.color, None);
+
+            let Widget::Input(input) = parse_widget(&format!("INPUT 0 0 20 x {}", text)).unwrap()
+            else {
+                panic!("Parsed value is not an input");
+            };
+            assert_eq!(input.value, text);
+
+            let Widget::Input(input) =
+                parse_widget(&format!("TEXTAREA 0 0 20 2 x {}", text)).unwrap()
+            else {
+                panic!("Parsed value is not an input");
+            };
+            assert_eq!(input.value, text);
+        }
+
+        let Widget::Input(input) = parse_widget("INPUT 0 0 10 x TRIM me").unwrap() else {
+            panic!("Parsed value is not an input");
+        };
+        assert_eq!(input.value, "TRIM me");
+        assert!(input.transforms.is_empty());
+
+        let Widget::Shape(shape) = parse_widget("BOX 1 2 40 6 IC title").unwrap() else {
+            panic!("Parsed value is not a shape");
+        };
+        assert_eq!(
+            shape,
+            Shape::frame((1, 2), 40, 6).with_title("IC title").build()
+        );
+
+        // Labels and shapes only take their own attributes
+        assert!(parse_widget("LABEL IC 0 0 text").is_err());
+        assert!(parse_widget("BOX HILIGHT=BLINK 1 2 40 6").is_err());
+    }
+
     #[test]
     fn test_parse_input() {
         let Widget::Input(input) = parse_widget("INPUT 5 111 10 nafn texti hér").unwrap() else {
@@ -191,4 +806,391 @@ mod tests {
         assert_eq!(id, "id".to_string());
         assert_eq!(value, "langur texti".to_string());
     }
+
+    #[test]
+    fn test_parse_label_options() {
+        let Widget::Label(label) =
+            parse_widget("LABEL COLOR=YELLOW HILIGHT=REVERSE 1 2 texti hér").unwrap()
+        else {
+            panic!("Parsed value is not a label");
+        };
+
+        assert_eq!(label.text, "texti hér");
+        assert_eq!(label.color, Some(Color::Yellow));
+        assert_eq!(label.highlight, Some(Highlight::Reverse));
+    }
+
+    #[test]
+    fn test_parse_input_options() {
+        let Widget::Input(input) =
+            parse_widget("INPUT COLOR=TURQUOISE 5 111 10 nafn texti hér").unwrap()
+        else {
+            panic!("Parsed value is not an input");
+        };
+
+        assert_eq!(input.value, "texti hér".to_string());
+        assert_eq!(input.color, Some(Color::Turquoise));
+        assert_eq!(input.highlight, None);
+        assert_eq!(input.max_length, None);
+    }
+
+    #[test]
+    fn test_parse_input_max_length() {
+        let Widget::Input(input) = parse_widget("INPUT MAXLEN=200 5 11 10 path /tmp").unwrap()
+        else {
+            panic!("Parsed value is not an input");
+        };
+
+        assert_eq!(input.length, 10);
+        assert_eq!(input.max_length, Some(200));
+        assert_eq!(input.value, "/tmp".to_string());
+    }
+
+    #[test]
+    fn test_parse_text_area() {
+        let Widget::Input(input) = parse_widget("TEXTAREA 5 11 40 4 nafn texti hér").unwrap()
+        else {
+            panic!("Parsed value is not an input");
+        };
+
+        assert_eq!(input.pos, (5, 11).into());
+        assert_eq!(input.length, 40);
+        assert_eq!(input.height, 4);
+        assert_eq!(input.name, "nafn".to_string());
+        assert_eq!(input.value, "texti hér".to_string());
+    }
+
+    #[test]
+    fn test_parse_checkbox() {
+        let Widget::Input(input) = parse_widget("CHECKBOX 5 11 agree true").unwrap() else {
+            panic!("Parsed value is not an input");
+        };
+
+        assert_eq!(input.pos, (5, 11).into());
+        assert_eq!(input.kind, InputKind::Checkbox);
+        assert_eq!(input.value, "true".to_string());
+
+        assert!(parse_widget("CHECKBOX 5 11 agree maybe").is_err());
+    }
+
+    #[test]
+    fn test_parse_radio() {
+        let Widget::Input(input) = parse_widget("RADIO 5 11 size large").unwrap() else {
+            panic!("Parsed value is not an input");
+        };
+
+        assert_eq!(input.name, "size".to_string());
+        assert_eq!(
+            input.kind,
+            InputKind::Radio {
+                id: "large".to_string()
+            }
+        );
+        assert!(!input.is_checked());
+    }
+
+    #[test]
+    fn test_parse_date_time() {
+        let Widget::Input(input) = parse_widget("DATE 5 11 born EUR 24.12.2024").unwrap() else {
+            panic!("Parsed value is not an input");
+        };
+
+        assert_eq!(input.format, Some(Format::Date(DateFormat::European)));
+        assert_eq!(input.length, 10);
+        assert_eq!(input.value, "24.12.2024".to_string());
+        assert_eq!(input.default_value, "24.12.2024".to_string());
+
+        // The default layout is ISO, an initial value must match the layout
+        assert!(parse_widget("DATE 5 11 born 24.12.2024").is_err());
+        assert!(parse_widget("DATE 5 11 born EUR 31.04.2024").is_err());
+        assert!(parse_widget("DATE 5 11 born 2024-12-24").is_ok());
+
+        let Widget::Input(input) = parse_widget("TIME 5 11 at").unwrap() else {
+            panic!("Parsed value is not an input");
+        };
+
+        assert_eq!(input.format, Some(Format::Time(TimeFormat::HourMinute)));
+
+        assert!(parse_widget("TIME 5 11 at ISO").is_err());
+    }
+
+    #[test]
+    fn test_parse_picture() {
+        let Widget::Input(input) = parse_widget("INPUT PIC=999-99-9999 RAW 5 11 0 ssn").unwrap()
+        else {
+            panic!("Parsed value is not an input");
+        };
+
+        assert_eq!(input.length, 11);
+        assert_eq!(input.mask, Some(Mask::from_picture("999-99-9999").unwrap()));
+        assert!(input.raw_output);
+        assert_eq!(input.value, "".to_string());
+
+        let Widget::Input(input) = parse_widget("INPUT 5 11 10 name RAWHIDE").unwrap() else {
+            panic!("Parsed value is not an input");
+        };
+
+        assert!(!input.raw_output);
+        assert_eq!(input.value, "RAWHIDE".to_string());
+
+        assert!(parse_widget("INPUT PIC=9(x) 5 11 0 amount").is_err());
+    }
+
+    #[test]
+    fn test_parse_number() {
+        let Widget::Input(input) =
+            parse_widget("NUMBER SIGNED DEC=2 THOUSANDS MIN=-100 5 11 10 amount -12.5").unwrap()
+        else {
+            panic!("Parsed value is not an input");
+        };
+
+        assert_eq!(
+            input.format,
+            Some(Format::Number(NumberFormat {
+                signed: true,
+                decimals: 2,
+                thousands: true,
+                min: Some(-100.0),
+                ..Default::default()
+            }))
+        );
+        assert_eq!(input.value, "    -12.50".to_string());
+        assert_eq!(input.output_value(), "-12.50".to_string());
+
+        let Widget::Input(input) = parse_widget("NUMBER ZEROFILL 5 11 4 count 7").unwrap() else {
+            panic!("Parsed value is not an input");
+        };
+
+        assert_eq!(input.value, "0007".to_string());
+
+        assert!(parse_widget("NUMBER 5 11 4 count -7").is_err());
+        assert!(parse_widget("NUMBER MAX=10 5 11 4 count 11").is_err());
+    }
+
+    #[test]
+    fn test_parse_transforms() {
+        let Widget::Input(input) = parse_widget("INPUT UPPER TRIM 18 2 8 dsname sys1").unwrap()
+        else {
+            panic!("Parsed value is not an input");
+        };
+
+        assert_eq!(input.transforms, vec![Transform::Upper, Transform::Trim]);
+        assert_eq!(input.value, "sys1".to_string());
+        assert_eq!(input.output_value(), "SYS1".to_string());
+    }
+
+    #[test]
+    fn test_parse_autoskip() {
+        let Widget::Input(input) = parse_widget("INPUT AUTOSKIP 18 2 8 account").unwrap() else {
+            panic!("Parsed value is not an input");
+        };
+        assert_eq!(input.autoskip, Some(true));
+
+        let Widget::Input(input) = parse_widget("NUMBER NOSKIP 18 2 8 count").unwrap() else {
+            panic!("Parsed value is not an input");
+        };
+        assert_eq!(input.autoskip, Some(false));
+    }
+
+    #[test]
+    fn test_parse_tab_order() {
+        let Widget::Input(input) = parse_widget("INPUT TAB=3 IC 18 2 8 account").unwrap() else {
+            panic!("Parsed value is not an input");
+        };
+        assert_eq!(input.tab_index, Some(3));
+        assert!(input.tab_stop);
+        assert!(input.initial_cursor);
+
+        let Widget::Input(input) = parse_widget("CHECKBOX NOTAB 18 4 agree true").unwrap() else {
+            panic!("Parsed value is not an input");
+        };
+        assert!(!input.tab_stop);
+        assert!(input.is_checked());
+    }
+
+    #[test]
+    fn test_parse_help() {
+        let Widget::Help(input, text) =
+            parse_widget("HELP username Enter your RACF user id").unwrap()
+        else {
+            panic!("Parsed value is not help");
+        };
+        assert_eq!(input.as_deref(), Some("username"));
+        assert_eq!(text, "Enter your RACF user id");
+
+        let mut form = Form::new((80, 24)).unwrap();
+        parse_str(&mut form, "HELP * Log on to the system").unwrap();
+        parse_str(&mut form, "HELP * Press Enter when done").unwrap();
+        assert_eq!(
+            form.help(),
+            Some("Log on to the system\nPress Enter when done")
+        );
+    }
+
+    #[test]
+    fn test_parse_output_and_hidden() {
+        let Widget::Input(input) = parse_widget("OUTPUT NOOUTPUT 18 2 20 status Active").unwrap()
+        else {
+            panic!("Parsed value is not an input");
+        };
+        assert!(input.protected);
+        assert!(!input.tab_stop);
+        assert!(!input.output);
+        assert_eq!(input.value, "Active");
+
+        let Widget::Input(input) = parse_widget("HIDDEN token abc 123").unwrap() else {
+            panic!("Parsed value is not an input");
+        };
+        assert!(input.hidden);
+        assert_eq!(input.name, "token");
+        assert_eq!(input.value, "abc 123");
+    }
+
+    #[test]
+    fn test_parse_shapes() {
+        let Widget::Shape(shape) = parse_widget("BOX COLOR=BLUE 1 2 40 6 Owner details").unwrap()
+        else {
+            panic!("Parsed value is not a shape");
+        };
+        assert_eq!(
+            shape,
+            Shape::frame((1, 2), 40, 6)
+                .with_title("Owner details")
+                .with_color(Color::Blue)
+                .build()
+        );
+
+        let Widget::Shape(shape) = parse_widget("VLINE 40 3 4").unwrap() else {
+            panic!("Parsed value is not a shape");
+        };
+        assert_eq!(shape, Shape::vline((40, 3), 4).build());
+
+        assert!(parse_widget("HLINE 1 12 78 junk").is_err());
+    }
+
+    #[test]
+    fn test_parse_hint() {
+        let mut form = Form::new((80, 24)).unwrap();
+        parse_str(&mut form, "DATE 18 2 start").unwrap();
+        parse_str(&mut form, "HINT start First day of the period").unwrap();
+        parse_str(&mut form, "PLACEHOLDER start YYYY-MM-DD").unwrap();
+
+        assert_eq!(form.get_field_and_data(), vec![("start", "".to_string())]);
+
+        let Widget::Placeholder(input, text) =
+            parse_widget("PLACEHOLDER start YYYY-MM-DD").unwrap()
+        else {
+            panic!("Parsed value is not a placeholder");
+        };
+        assert_eq!(input, "start");
+        assert_eq!(text, "YYYY-MM-DD");
+
+        assert!(parse_widget("HINT * text").is_err());
+    }
+
+    #[test]
+    fn test_parse_unknown_input() {
+        let mut form = Form::new((80, 24)).unwrap();
+        parse_str(&mut form, "DATE 18 2 start").unwrap();
+
+        for line in [
+            "HELP strat First day of the period",
+            "HINT strat First day of the period",
+            "PLACEHOLDER strat YYYY-MM-DD",
+            "SELECT strat today Today",
+        ] {
+            assert_eq!(
+                parse_str(&mut form, line),
+                Err("Input strat not found".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_page() {
+        let mut form = Form::new((80, 24)).unwrap();
+        parse_str(&mut form, "INPUT 18 2 8 first").unwrap();
+        parse_str(&mut form, "PAGE").unwrap();
+        parse_str(&mut form, "INPUT 18 2 8 second").unwrap();
+
+        assert_eq!(form.pages(), 2);
+        assert_eq!(
+            form.get_field_and_data(),
+            vec![("first", "".to_string()), ("second", "".to_string())]
+        );
+
+        assert!(parse_widget("PAGE 2").is_err());
+    }
+
+    #[test]
+    fn test_parse_group() {
+        let mut form = Form::new((80, 24)).unwrap();
+        parse_str(&mut form, "GROUP VISIBLE=2 MAX=10 item 3").unwrap();
+        parse_str(&mut form, "INPUT 2 6 4 qty").unwrap();
+        parse_str(&mut form, "INPUT 8 6 10 part").unwrap();
+        parse_str(&mut form, "END").unwrap();
+        parse_str(&mut form, "HINT qty Number of parts").unwrap();
+
+        let names: Vec<&str> = form
+            .get_field_and_data()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "item[0].qty",
+                "item[0].part",
+                "item[1].qty",
+                "item[1].part",
+                "item[2].qty",
+                "item[2].part"
+            ]
+        );
+
+        let Widget::Group(group) = parse_widget("GROUP VISIBLE=2 MAX=10 item 3").unwrap() else {
+            panic!("Parsed value is not a group");
+        };
+        assert_eq!((group.rows, group.visible, group.max_rows), (3, 2, 10));
+
+        assert!(parse_widget("GROUP item").is_err());
+    }
+
+    #[test]
+    fn test_parse_group_errors() {
+        // Group still open at the end of the file
+        let mut form = Form::new((80, 24)).unwrap();
+        parse_str(&mut form, "INPUT 2 4 8 a").unwrap();
+        parse_str(&mut form, "GROUP item 2").unwrap();
+        parse_str(&mut form, "INPUT 2 6 4 qty").unwrap();
+        assert_eq!(
+            parse_finish(&form),
+            Err("GROUP item without END".to_string())
+        );
+
+        // Nested group
+        assert_eq!(
+            parse_str(&mut form, "GROUP part 2"),
+            Err("GROUP part started before END of GROUP item".to_string())
+        );
+        parse_str(&mut form, "END").unwrap();
+        assert_eq!(parse_finish(&form), Ok(()));
+
+        // Stray END
+        assert_eq!(
+            parse_str(&mut form, "END"),
+            Err("END without GROUP".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_size() {
+        let Widget::Size(x, y) = parse_widget("SIZE 82 60").unwrap() else {
+            panic!("Parsed value is not a size");
+        };
+        assert_eq!((x, y), (82, 60));
+
+        assert!(parse_widget("SIZE 82").is_err());
+        assert!(parse_widget("SIZE 82 60 junk").is_err());
+    }
 }